@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use color_eyre::eyre::{eyre, WrapErr};
+
+use crate::{input::InputSource, Part};
+
+pub const DEFAULT_MANIFEST: &str = "answers.txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: Part,
+    pub input: InputSource,
+    pub answer: String,
+}
+
+impl Expected {
+    // Drawings end with a newline, which isn't worth recording
+    pub fn matches(&self, actual: &str) -> bool {
+        self.answer == actual.trim_end()
+    }
+}
+
+// One answer per line, `day part input answer`, with # comments
+pub fn parse_manifest(manifest: &str) -> color_eyre::Result<Vec<Expected>> {
+    manifest
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            parse_entry(line).wrap_err_with(|| format!("Invalid answer on line {}", index + 1))
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str) -> color_eyre::Result<Expected> {
+    let mut fields = line.splitn(4, ' ');
+    let mut next = |name| fields.next().ok_or_else(|| eyre!("Missing {}", name));
+    let day = next("day")?;
+    let day = day
+        .parse()
+        .wrap_err_with(|| format!("Invalid day {}", day))?;
+    let part = next("part")?.parse()?;
+    let input = next("input")?.parse()?;
+    let answer = next("answer")?.replace("\\n", "\n");
+    Ok(Expected {
+        day,
+        part,
+        input,
+        answer,
+    })
+}
+
+pub fn load_manifest(path: &Path) -> color_eyre::Result<Vec<Expected>> {
+    parse_manifest(&crate::input::read_file(path)?)
+        .wrap_err_with(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() -> color_eyre::Result<()> {
+        let manifest = "# comment\n\n7 2 test 24933642\n10 2 real #.\\n.#\n";
+        let expected = parse_manifest(manifest)?;
+        assert_eq!(
+            expected,
+            vec![
+                Expected {
+                    day: 7,
+                    part: Part::Two,
+                    input: InputSource::Test,
+                    answer: "24933642".to_owned(),
+                },
+                Expected {
+                    day: 10,
+                    part: Part::Two,
+                    input: InputSource::Real,
+                    answer: "#.\n.#".to_owned(),
+                },
+            ]
+        );
+        assert!(expected[1].matches("#.\n.#\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_manifest_error_has_line() {
+        let error = parse_manifest("1 1 real 5\n1 3 real 6\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid answer on line 2");
+    }
+
+    #[test]
+    fn test_checked_in_manifest_parses() -> color_eyre::Result<()> {
+        let expected = parse_manifest(include_str!("../answers.txt"))?;
+        assert!(!expected.is_empty());
+        Ok(())
+    }
+}
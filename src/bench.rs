@@ -0,0 +1,205 @@
+use std::{hint::black_box, str::FromStr, time::Duration};
+
+use color_eyre::eyre::eyre;
+use itertools::Itertools;
+
+use crate::{Part, Puzzle};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+    // Runs thrown away before measuring, to fill caches and let the CPU clock up
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let sorted = samples.iter().copied().sorted().collect_vec();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (*sorted.get(middle.checked_sub(1)?)? + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        Some(Stats {
+            min: *sorted.first()?,
+            median,
+            max: *sorted.last()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Benchmark {
+    pub day: u32,
+    pub part: Part,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+pub fn run(
+    puzzle: &dyn Puzzle,
+    part: Part,
+    input: &str,
+    example: bool,
+    settings: Settings,
+) -> color_eyre::Result<Benchmark> {
+    if settings.iterations == 0 {
+        return Err(eyre!("Need at least one iteration to benchmark"));
+    }
+    for _ in 0..settings.warmup {
+        black_box(puzzle.run(part, black_box(input), example)?);
+    }
+    let (parse, solve): (Vec<_>, Vec<_>) = (0..settings.iterations)
+        .map(|_| {
+            let timed = black_box(puzzle.run(part, black_box(input), example)?);
+            Ok((timed.parse, timed.solve))
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    Ok(Benchmark {
+        day: puzzle.day(),
+        part,
+        iterations: settings.iterations,
+        parse: Stats::from_samples(&parse).unwrap(),
+        solve: Stats::from_samples(&solve).unwrap(),
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(eyre!("Invalid format {}, expected table, csv or json", s)),
+        }
+    }
+}
+
+// Column names shared by the table and csv output, all times are in µs
+pub const COLUMNS: [&str; 9] = [
+    "day",
+    "part",
+    "iterations",
+    "parse_min_us",
+    "parse_median_us",
+    "parse_max_us",
+    "solve_min_us",
+    "solve_median_us",
+    "solve_max_us",
+];
+
+impl Benchmark {
+    pub fn values(&self) -> [String; 9] {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.iterations.to_string(),
+            self.parse.min.as_micros().to_string(),
+            self.parse.median.as_micros().to_string(),
+            self.parse.max.as_micros().to_string(),
+            self.solve.min.as_micros().to_string(),
+            self.solve.median.as_micros().to_string(),
+            self.solve.max.as_micros().to_string(),
+        ]
+    }
+}
+
+pub fn to_csv(benchmarks: &[Benchmark]) -> String {
+    std::iter::once(COLUMNS.join(","))
+        .chain(benchmarks.iter().map(|b| b.values().join(",")))
+        .map(|line| line + "\n")
+        .collect()
+}
+
+// Every value is a number so there's nothing to escape
+pub fn to_json(benchmarks: &[Benchmark]) -> String {
+    let objects = benchmarks
+        .iter()
+        .map(|b| {
+            let fields = COLUMNS
+                .iter()
+                .zip(b.values())
+                .map(|(name, value)| format!("\"{}\": {}", name, value))
+                .join(", ");
+            format!("  {{{}}}", fields)
+        })
+        .join(",\n");
+    if objects.is_empty() {
+        "[]\n".to_owned()
+    } else {
+        format!("[\n{}\n]\n", objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&micros(&[7, 1, 4])).unwrap();
+        assert_eq!(
+            stats,
+            Stats {
+                min: Duration::from_micros(1),
+                median: Duration::from_micros(4),
+                max: Duration::from_micros(7),
+            }
+        );
+        let even = Stats::from_samples(&micros(&[8, 2, 4, 6])).unwrap();
+        assert_eq!(even.median, Duration::from_micros(5));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_output() -> color_eyre::Result<()> {
+        let puzzle = crate::days::get(7).unwrap();
+        let input = include_str!("../input/day7.test.txt");
+        let settings = Settings {
+            warmup: 0,
+            iterations: 3,
+        };
+        let benchmarks = [run(puzzle, Part::One, input, true, settings)?];
+        assert_eq!(benchmarks[0].iterations, 3);
+        assert!(benchmarks[0].solve.min <= benchmarks[0].solve.median);
+        let csv = to_csv(&benchmarks);
+        assert!(csv.starts_with("day,part,iterations,parse_min_us,"));
+        assert!(csv.lines().nth(1).unwrap().starts_with("7,1,3,"));
+        let json = to_json(&benchmarks);
+        assert!(json.starts_with("[\n  {\"day\": 7, \"part\": 1, \"iterations\": 3, "));
+        Ok(())
+    }
+}
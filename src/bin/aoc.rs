@@ -0,0 +1,618 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use advent_of_code2022::{
+    answers::{self, Expected},
+    bench::{self, Benchmark, Format},
+    days::{
+        self,
+        day6::{self, Day6},
+        day7::{Day7, Find},
+    },
+    input::InputSource,
+    parsers::name_input,
+    simulation::{self, Recorder},
+    Part, Puzzle, Solution,
+};
+use color_eyre::eyre::{bail, eyre, WrapErr};
+use itertools::Itertools;
+
+const USAGE: &str = "Usage: aoc <day|all> [--part <1|2>] [--input <real|test|test2|PATH|->]
+       aoc <day> --record PATH [--every N] [--steps N] [--part ...] [--input ...]
+       aoc <day> --param NAME=VALUE [--param ...] [--part ...] [--input ...]
+       aoc bench <day|all> [--part <1|2>] [--input ...] [--warmup N] [--iterations N]
+                 [--format <table|csv|json>]
+       aoc verify [--part <1|2>] [--manifest PATH]
+       aoc fs <tree|du PATH|find NAME|min-size N> [--input ...]
+       aoc markers <packet|message|N> [--input ...]
+
+--input defaults to real, which reads input/dayN.txt. test and test2 read
+input/dayN.test.txt and input/dayN.test.2.txt, - reads stdin. Set
+AOC_INPUT_DIR to look somewhere other than input/.
+
+bench times parsing and solving separately, reporting min/median/max in µs
+over --iterations runs (default 10) after --warmup runs (default 3).
+
+verify checks every answer recorded in answers.txt, or the --manifest given.
+
+--param changes one of a day's settings from what the puzzle uses: capacity,
+required_free and minimum_set (true to delete the least space using any
+number of directories) for day 7, row and max_bound for day 15, and grid_size
+for day 22.
+
+fs looks round the filesystem in day 7's terminal log: the whole tree, the size
+of a file or directory, or everything with a name or of at least a size.
+
+markers finds where the first marker in day 6's signal ends: 4 different
+characters for packet, 14 for message, or N. The signal is read as it goes, so
+it can be too big to fit in memory.
+
+--record plays out a day's simulation (days 5, 9, 14, 17, 22 and 23) and
+writes it to PATH, as an animated SVG if PATH ends in .svg and as text frames
+otherwise. A frame is captured every N steps (default 1), stopping after
+--steps steps if given. Recording both parts adds -part1 and -part2 to the
+file name.
+
+Set RUST_LOG=debug to see extra detail from the solutions, like how often
+cached results were reused.";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Run,
+    Bench,
+    Verify,
+    Fs,
+    Markers,
+}
+
+// What to ask of day 7's filesystem
+enum Query {
+    Tree,
+    Du(String),
+    Find(String),
+    MinSize(u64),
+}
+
+enum Target {
+    All,
+    Day(&'static dyn Puzzle),
+}
+
+struct Args {
+    mode: Mode,
+    target: Target,
+    part: Option<Part>,
+    input: InputSource,
+    manifest: PathBuf,
+    bench: bench::Settings,
+    format: Format,
+    record: Option<PathBuf>,
+    recorder: Recorder,
+    query: Option<Query>,
+    // how many different characters make a marker, for aoc markers
+    window: Option<usize>,
+    params: Vec<(String, String)>,
+}
+
+fn parse_query<'a>(args: &mut impl Iterator<Item = &'a str>) -> color_eyre::Result<Query> {
+    let query = args.next().ok_or_else(|| eyre!(USAGE))?;
+    if query == "tree" {
+        return Ok(Query::Tree);
+    }
+    let value = args
+        .next()
+        .ok_or_else(|| eyre!("Missing value for fs {}\n{}", query, USAGE))?;
+    match query {
+        "du" => Ok(Query::Du(value.to_owned())),
+        "find" => Ok(Query::Find(value.to_owned())),
+        "min-size" => Ok(Query::MinSize(
+            value
+                .parse()
+                .wrap_err_with(|| format!("Invalid size {}", value))?,
+        )),
+        _ => bail!("Unknown fs query {}\n{}", query, USAGE),
+    }
+}
+
+fn parse_window(arg: Option<&str>) -> color_eyre::Result<usize> {
+    match arg {
+        Some("packet") => Ok(day6::PACKET),
+        Some("message") => Ok(day6::MESSAGE),
+        Some(window) => match window.parse() {
+            Ok(0) | Err(_) => bail!("Invalid marker length {}", window),
+            Ok(window) => Ok(window),
+        },
+        None => bail!(USAGE),
+    }
+}
+
+fn parse_target(arg: Option<&str>) -> color_eyre::Result<Target> {
+    match arg {
+        Some("all") => Ok(Target::All),
+        Some(day) => {
+            let day = day
+                .parse::<u32>()
+                .wrap_err_with(|| format!("Invalid day {}", day))?;
+            let puzzle = days::get(day).ok_or_else(|| eyre!("Day {} is not implemented", day))?;
+            Ok(Target::Day(puzzle))
+        }
+        None => bail!(USAGE),
+    }
+}
+
+fn parse_args(args: &[String]) -> color_eyre::Result<Args> {
+    let mut args = args.iter().map(|s| s.as_str());
+    let mut query = None;
+    let mut window = None;
+    let (mode, target) = match args.next() {
+        Some("verify") => (Mode::Verify, Target::All),
+        Some("bench") => (Mode::Bench, parse_target(args.next())?),
+        Some("fs") => {
+            query = Some(parse_query(&mut args)?);
+            (Mode::Fs, Target::Day(&Day7))
+        }
+        Some("markers") => {
+            window = Some(parse_window(args.next())?);
+            (Mode::Markers, Target::Day(&Day6))
+        }
+        arg => (Mode::Run, parse_target(arg)?),
+    };
+    let mut part = None;
+    let mut input = InputSource::Real;
+    let mut manifest = PathBuf::from(answers::DEFAULT_MANIFEST);
+    let mut settings = bench::Settings::default();
+    let mut format = Format::Table;
+    let mut record = None;
+    let mut recorder = Recorder::default();
+    let mut params = vec![];
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| eyre!("Missing value for {}\n{}", flag, USAGE))?;
+        match (flag, mode) {
+            ("--part", _) => part = Some(value.parse()?),
+            ("--input", Mode::Run | Mode::Bench | Mode::Fs | Mode::Markers) => {
+                input = value.parse()?
+            }
+            ("--manifest", Mode::Verify) => manifest = PathBuf::from(value),
+            ("--warmup", Mode::Bench) => {
+                settings.warmup = value
+                    .parse()
+                    .wrap_err_with(|| format!("Invalid warmup {}", value))?
+            }
+            ("--iterations", Mode::Bench) => {
+                settings.iterations = value
+                    .parse()
+                    .wrap_err_with(|| format!("Invalid iterations {}", value))?
+            }
+            ("--format", Mode::Bench) => format = value.parse()?,
+            ("--record", Mode::Run) => record = Some(PathBuf::from(value)),
+            ("--every", Mode::Run) => {
+                recorder.every = value
+                    .parse()
+                    .wrap_err_with(|| format!("Invalid every {}", value))?
+            }
+            ("--steps", Mode::Run) => {
+                recorder.limit = Some(
+                    value
+                        .parse()
+                        .wrap_err_with(|| format!("Invalid steps {}", value))?,
+                )
+            }
+            ("--param", Mode::Run) => {
+                let (name, value) = value
+                    .split_once('=')
+                    .ok_or_else(|| eyre!("Invalid param {}, expected NAME=VALUE", value))?;
+                params.push((name.to_owned(), value.to_owned()));
+            }
+            _ => bail!("Unknown argument {}\n{}", flag, USAGE),
+        }
+    }
+    if matches!(target, Target::All) && !input.is_per_day() {
+        bail!("--input {} can only be used with a single day", input);
+    }
+    if matches!(target, Target::All) && record.is_some() {
+        bail!("--record can only be used with a single day");
+    }
+    if matches!(target, Target::All) && !params.is_empty() {
+        bail!("--param can only be used with a single day");
+    }
+    if let Some(path) = &record {
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            recorder.format = simulation::Format::Svg;
+        }
+    }
+    Ok(Args {
+        mode,
+        target,
+        part,
+        input,
+        manifest,
+        bench: settings,
+        format,
+        record,
+        recorder,
+        query,
+        window,
+        params,
+    })
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+    simple_logger::SimpleLogger::new()
+        .with_level(log::LevelFilter::Warn)
+        .env()
+        .init()?;
+    let args = parse_args(&std::env::args().skip(1).collect_vec())?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    match (args.mode, args.target) {
+        (Mode::Run, Target::Day(puzzle)) => match &args.record {
+            Some(path) => record_day(
+                puzzle,
+                &parts,
+                &args.input,
+                &args.params,
+                &args.recorder,
+                path,
+            ),
+            None => run_day(puzzle, &parts, &args.input, &args.params),
+        },
+        (Mode::Run, Target::All) => {
+            run_all(&parts, &args.input);
+            Ok(())
+        }
+        (Mode::Bench, Target::Day(puzzle)) => {
+            run_bench(&[puzzle], &parts, &args.input, args.bench, args.format)
+        }
+        (Mode::Bench, Target::All) => {
+            run_bench(&days::DAYS, &parts, &args.input, args.bench, args.format)
+        }
+        (Mode::Verify, _) => run_verify(&parts, &args.manifest),
+        (Mode::Fs, _) => match &args.query {
+            Some(query) => run_fs(query, &args.input),
+            None => bail!(USAGE),
+        },
+        (Mode::Markers, _) => match args.window {
+            Some(window) => run_markers(window, &args.input),
+            None => bail!(USAGE),
+        },
+    }
+}
+
+fn run_markers(window: usize, source: &InputSource) -> color_eyre::Result<()> {
+    let reader = source.open(Day6::DAY)?;
+    let end = day6::first_marker(reader, window)
+        .wrap_err_with(|| format!("Failed to find a marker in {}", source.name(Day6::DAY)))?;
+    println!("{}", end);
+    Ok(())
+}
+
+fn run_fs(query: &Query, source: &InputSource) -> color_eyre::Result<()> {
+    let input = source.read(Day7::DAY)?;
+    let fs = Day7::parse(&input).map_err(|e| name_input(e, &source.name(Day7::DAY)))?;
+    let entries = match query {
+        Query::Tree => {
+            print!("{}", fs.tree());
+            return Ok(());
+        }
+        Query::Du(path) => {
+            let size = fs
+                .du(path)
+                .ok_or_else(|| eyre!("{} isn't in the terminal log", path))?;
+            println!("{}", size);
+            return Ok(());
+        }
+        Query::Find(name) => fs.find(Find::Name(name)),
+        Query::MinSize(size) => fs.find(Find::MinSize(*size)),
+    };
+    for entry in entries {
+        let kind = if entry.is_dir { "dir" } else { "file" };
+        println!("{}\t{}\t{}", entry.size, kind, entry.path);
+    }
+    Ok(())
+}
+
+fn run_day(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    source: &InputSource,
+    params: &[(String, String)],
+) -> color_eyre::Result<()> {
+    let day = puzzle.day();
+    let input = source.read(day)?;
+    println!("Day {}", day);
+    for &part in parts {
+        if !puzzle.has_part(part) {
+            continue;
+        }
+        let timed = puzzle
+            .run_with(part, &input, source.is_example(), params)
+            .map_err(|e| name_input(e, &source.name(day)))?;
+        println!(
+            "Part {}:{} in {:.1?} (parse {:.1?})",
+            part,
+            show_answer(&timed.answer),
+            timed.parse + timed.solve,
+            timed.parse
+        );
+    }
+    Ok(())
+}
+
+fn record_day(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    source: &InputSource,
+    params: &[(String, String)],
+    recorder: &Recorder,
+    path: &Path,
+) -> color_eyre::Result<()> {
+    let day = puzzle.day();
+    let input = source.read(day)?;
+    let parts = parts
+        .iter()
+        .copied()
+        .filter(|&part| puzzle.has_part(part))
+        .collect_vec();
+    let mut recorded = 0;
+    for &part in &parts {
+        let recording = puzzle
+            .record(part, &input, source.is_example(), params, recorder)
+            .map_err(|e| name_input(e, &source.name(day)))?;
+        let Some(recording) = recording else {
+            println!("Day {} part {} has no simulation to record", day, part);
+            continue;
+        };
+        recorded += 1;
+        let path = if parts.len() > 1 {
+            part_path(path, part)
+        } else {
+            path.to_owned()
+        };
+        std::fs::write(&path, recording)
+            .wrap_err_with(|| format!("Couldn't write {}", path.display()))?;
+        println!("Day {} part {} recorded to {}", day, part, path.display());
+    }
+    if recorded == 0 {
+        bail!("Day {} has no simulation to record", day);
+    }
+    Ok(())
+}
+
+// out.svg becomes out-part1.svg
+fn part_path(path: &Path, part: Part) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-part{}.{}", stem, part, extension.to_string_lossy()),
+        None => format!("{}-part{}", stem, part),
+    };
+    path.with_file_name(name)
+}
+
+struct Row {
+    day: u32,
+    answers: Vec<String>,
+    elapsed: Duration,
+}
+
+fn run_all(parts: &[Part], source: &InputSource) {
+    // answers that don't fit in the table get printed underneath it
+    let mut notes = vec![];
+    let rows = days::DAYS
+        .iter()
+        .map(|puzzle| {
+            let day = puzzle.day();
+            let time = Instant::now();
+            let input = source.read(day);
+            let answers = parts
+                .iter()
+                .map(|&part| {
+                    if !puzzle.has_part(part) {
+                        return "-".to_owned();
+                    }
+                    let result = match &input {
+                        Ok(input) => puzzle
+                            .solve(part, input, source.is_example())
+                            .map_err(|e| name_input(e, &source.name(day))),
+                        Err(e) => Err(eyre!("{}", e)),
+                    };
+                    match result {
+                        Ok(answer) if answer.contains('\n') => {
+                            notes.push(format!("Day {} part {}:\n{}", day, part, answer));
+                            "(see below)".to_owned()
+                        }
+                        Ok(answer) => answer,
+                        Err(e) => {
+                            notes.push(format!("Day {} part {} failed: {:#}", day, part, e));
+                            "error".to_owned()
+                        }
+                    }
+                })
+                .collect_vec();
+            Row {
+                day,
+                answers,
+                elapsed: time.elapsed(),
+            }
+        })
+        .collect_vec();
+    let headers = std::iter::once("Day".to_owned())
+        .chain(parts.iter().map(|part| format!("Part {}", part)))
+        .chain(std::iter::once("Time".to_owned()))
+        .collect_vec();
+    let cells = rows
+        .iter()
+        .map(|row| {
+            std::iter::once(row.day.to_string())
+                .chain(row.answers.iter().cloned())
+                .chain(std::iter::once(format!("{:.1?}", row.elapsed)))
+                .collect_vec()
+        })
+        .collect_vec();
+    print_table(&headers, &cells);
+    for note in notes {
+        println!();
+        println!("{}", note);
+    }
+}
+
+fn run_bench(
+    puzzles: &[&dyn Puzzle],
+    parts: &[Part],
+    source: &InputSource,
+    settings: bench::Settings,
+    format: Format,
+) -> color_eyre::Result<()> {
+    let mut benchmarks = vec![];
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        let input = source.read(day)?;
+        for &part in parts.iter().filter(|&&part| puzzle.has_part(part)) {
+            // progress goes to stderr so the output can be redirected to a file
+            eprintln!("Benchmarking day {} part {}", day, part);
+            benchmarks.push(
+                bench::run(*puzzle, part, &input, source.is_example(), settings)
+                    .map_err(|e| name_input(e, &source.name(day)))?,
+            );
+        }
+    }
+    match format {
+        Format::Table => print_bench_table(&benchmarks),
+        Format::Csv => print!("{}", bench::to_csv(&benchmarks)),
+        Format::Json => print!("{}", bench::to_json(&benchmarks)),
+    }
+    Ok(())
+}
+
+fn print_bench_table(benchmarks: &[Benchmark]) {
+    let headers = [
+        "Day",
+        "Part",
+        "Runs",
+        "Parse min",
+        "median",
+        "max",
+        "Solve min",
+        "median",
+        "max",
+    ]
+    .map(String::from);
+    let cells = benchmarks
+        .iter()
+        .map(|benchmark| {
+            benchmark
+                .values()
+                .into_iter()
+                .enumerate()
+                // the first three columns are counts, the rest are times
+                .map(|(i, value)| if i < 3 { value } else { value + "µs" })
+                .collect_vec()
+        })
+        .collect_vec();
+    print_table(&headers, &cells);
+}
+
+fn run_verify(parts: &[Part], manifest: &Path) -> color_eyre::Result<()> {
+    let expected = answers::load_manifest(manifest)?;
+    if let Some(unknown) = expected.iter().find(|e| days::get(e.day).is_none()) {
+        bail!(
+            "{} has answers for day {} which isn't implemented",
+            manifest.display(),
+            unknown.day
+        );
+    }
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for puzzle in days::DAYS {
+        let day = puzzle.day();
+        for &part in parts.iter().filter(|&&part| puzzle.has_part(part)) {
+            let entries = expected
+                .iter()
+                .filter(|e| e.day == day && e.part == part)
+                .collect_vec();
+            if entries.is_empty() {
+                println!("Day {} part {}: MISSING", day, part);
+                missing += 1;
+            }
+            for entry in entries {
+                if verify(puzzle, entry) {
+                    passed += 1;
+                } else {
+                    failed += 1;
+                }
+            }
+        }
+    }
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        bail!("{} answers no longer match {}", failed, manifest.display());
+    }
+    Ok(())
+}
+
+fn verify(puzzle: &dyn Puzzle, entry: &Expected) -> bool {
+    let label = format!("Day {} part {} ({})", entry.day, entry.part, entry.input);
+    let result = entry.input.read(entry.day).and_then(|input| {
+        puzzle
+            .solve(entry.part, &input, entry.input.is_example())
+            .map_err(|e| name_input(e, &entry.input.name(entry.day)))
+    });
+    match result {
+        Ok(actual) if entry.matches(&actual) => {
+            println!("{}: PASS", label);
+            true
+        }
+        Ok(actual) => {
+            println!("{}: FAIL", label);
+            println!("  expected:{}", show_answer(&entry.answer));
+            println!("  actual:{}", show_answer(actual.trim_end()));
+            false
+        }
+        Err(e) => {
+            println!("{}: FAIL {:#}", label, e);
+            false
+        }
+    }
+}
+
+// drawings start on their own line so the first row lines up
+fn show_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}", answer)
+    } else {
+        format!(" {}", answer)
+    }
+}
+
+fn print_table(headers: &[String], cells: &[Vec<String>]) {
+    let widths = (0..headers.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].len())
+                .chain(std::iter::once(headers[i].len()))
+                .max()
+                .unwrap()
+        })
+        .collect_vec();
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .join(" | ")
+    };
+    println!("{}", format_row(headers));
+    println!(
+        "{}",
+        widths.iter().map(|&width| "-".repeat(width)).join("-+-")
+    );
+    for row in cells {
+        println!("{}", format_row(row));
+    }
+}
@@ -0,0 +1,290 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use color_eyre::eyre::eyre;
+use itertools::Itertools;
+use num::{range_inclusive, PrimInt, Signed};
+
+// A point in space, for puzzles that add a third axis to Coordinate
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Coordinate3<T: PrimInt> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: PrimInt> Coordinate3<T> {
+    pub fn new(x: T, y: T, z: T) -> Coordinate3<T> {
+        Coordinate3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Coordinate3<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    // The six cells sharing a face, skipping anything past the limits of T
+    pub fn neighbours(&self) -> Vec<Coordinate3<T>> {
+        self.offsets()
+            .filter(|&(dx, dy, dz)| dx * dx + dy * dy + dz * dz == 1)
+            .filter_map(|delta| self.step(delta))
+            .collect()
+    }
+
+    // The 26 cells sharing a face, edge or corner
+    pub fn neighbours_diagonal(&self) -> Vec<Coordinate3<T>> {
+        self.offsets()
+            .filter(|&delta| delta != (0, 0, 0))
+            .filter_map(|delta| self.step(delta))
+            .collect()
+    }
+
+    fn offsets(&self) -> impl Iterator<Item = (i8, i8, i8)> {
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .cartesian_product(-1..=1)
+            .map(|((dx, dy), dz)| (dx, dy, dz))
+    }
+
+    fn step(&self, (dx, dy, dz): (i8, i8, i8)) -> Option<Coordinate3<T>> {
+        let step = |value: T, delta: i8| match delta {
+            -1 => value.checked_sub(&T::one()),
+            1 => value.checked_add(&T::one()),
+            _ => Some(value),
+        };
+        Some(Coordinate3 {
+            x: step(self.x, dx)?,
+            y: step(self.y, dy)?,
+            z: step(self.z, dz)?,
+        })
+    }
+
+    pub fn cast<U: PrimInt>(&self) -> Option<Coordinate3<U>> {
+        Some(Coordinate3 {
+            x: U::from(self.x)?,
+            y: U::from(self.y)?,
+            z: U::from(self.z)?,
+        })
+    }
+}
+
+fn abs_diff<T: PrimInt>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: PrimInt> Add for Coordinate3<T> {
+    type Output = Coordinate3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Coordinate3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: PrimInt> AddAssign for Coordinate3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: PrimInt> Sub for Coordinate3<T> {
+    type Output = Coordinate3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coordinate3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: PrimInt> SubAssign for Coordinate3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Coordinate3<T> {
+    type Output = Coordinate3<T>;
+
+    fn neg(self) -> Self::Output {
+        Coordinate3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: PrimInt> Mul<T> for Coordinate3<T> {
+    type Output = Coordinate3<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Coordinate3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+// Three comma separated numbers, like "2,-1,5"
+impl<T: PrimInt + FromStr> FromStr for Coordinate3<T> {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<T>()
+                    .map_err(|_| eyre!("Invalid number {:?} in {:?}", value, s))
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        match values[..] {
+            [x, y, z] => Ok(Coordinate3 { x, y, z }),
+            _ => Err(eyre!("Expected x,y,z but got {:?}", s)),
+        }
+    }
+}
+
+impl<T: PrimInt + Display> Display for Coordinate3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+// Smallest box holding a set of points, inclusive at both ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds3<T: PrimInt> {
+    pub min: Coordinate3<T>,
+    pub max: Coordinate3<T>,
+}
+
+impl<T: PrimInt> Bounds3<T> {
+    pub fn new(coordinate: Coordinate3<T>) -> Bounds3<T> {
+        Bounds3 {
+            min: coordinate,
+            max: coordinate,
+        }
+    }
+
+    // None if there are no points
+    pub fn from_coordinates<I>(coordinates: I) -> Option<Bounds3<T>>
+    where
+        I: IntoIterator<Item = Coordinate3<T>>,
+    {
+        let mut coordinates = coordinates.into_iter();
+        let mut bounds = Bounds3::new(coordinates.next()?);
+        for coordinate in coordinates {
+            bounds.expand(coordinate);
+        }
+        Some(bounds)
+    }
+
+    pub fn expand(&mut self, coordinate: Coordinate3<T>) {
+        self.min.x = self.min.x.min(coordinate.x);
+        self.min.y = self.min.y.min(coordinate.y);
+        self.min.z = self.min.z.min(coordinate.z);
+        self.max.x = self.max.x.max(coordinate.x);
+        self.max.y = self.max.y.max(coordinate.y);
+        self.max.z = self.max.z.max(coordinate.z);
+    }
+
+    // Pad every side, e.g. to leave room to walk around the outside
+    pub fn grow(&self, margin: T) -> Bounds3<T> {
+        let margin = Coordinate3::new(margin, margin, margin);
+        Bounds3 {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    pub fn contains(&self, coordinate: Coordinate3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&coordinate.x)
+            && (self.min.y..=self.max.y).contains(&coordinate.y)
+            && (self.min.z..=self.max.z).contains(&coordinate.z)
+    }
+
+    pub fn volume(&self) -> usize {
+        let size = self.max - self.min;
+        (size.x.to_usize().unwrap() + 1)
+            * (size.y.to_usize().unwrap() + 1)
+            * (size.z.to_usize().unwrap() + 1)
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate3<T>> {
+        range_inclusive(self.min.x, self.max.x)
+            .cartesian_product(range_inclusive(self.min.y, self.max.y))
+            .cartesian_product(range_inclusive(self.min.z, self.max.z))
+            .map(|((x, y), z)| Coordinate3 { x, y, z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Coordinate3::new(1, 2, 3);
+        let b = Coordinate3::new(-1, 0, 5);
+        assert_eq!(a + b, Coordinate3::new(0, 2, 8));
+        assert_eq!(a - b, Coordinate3::new(2, 2, -2));
+        assert_eq!(-a * 2, Coordinate3::new(-2, -4, -6));
+        assert_eq!(a.manhattan(&b), 6);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Coordinate3::new(0, 0, 0);
+        let neighbours = origin.neighbours();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.iter().all(|n| n.manhattan(&origin) == 1));
+        assert_eq!(origin.neighbours_diagonal().len(), 26);
+        // unsigned coordinates stop at zero
+        let corner = Coordinate3::<u32>::new(0, 0, 0);
+        assert_eq!(corner.neighbours().len(), 3);
+        assert_eq!(corner.neighbours_diagonal().len(), 7);
+    }
+
+    #[test]
+    fn test_parse() -> color_eyre::Result<()> {
+        let parsed: Coordinate3<i32> = "2,-1,5".parse()?;
+        assert_eq!(parsed, Coordinate3::new(2, -1, 5));
+        assert_eq!(parsed.to_string(), "2,-1,5");
+        assert!("1,2".parse::<Coordinate3<i32>>().is_err());
+        assert!("1,2,3,4".parse::<Coordinate3<i32>>().is_err());
+        assert!("1,x,3".parse::<Coordinate3<i32>>().is_err());
+        assert!("1,-2,3".parse::<Coordinate3<u32>>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds =
+            Bounds3::from_coordinates([Coordinate3::new(1, 5, -2), Coordinate3::new(3, 2, 0)])
+                .unwrap();
+        assert_eq!(bounds.min, Coordinate3::new(1, 2, -2));
+        assert_eq!(bounds.max, Coordinate3::new(3, 5, 0));
+        assert_eq!(bounds.volume(), 3 * 4 * 3);
+        assert_eq!(bounds.coordinates().count(), bounds.volume());
+        assert!(bounds.contains(Coordinate3::new(2, 3, -1)));
+        assert!(!bounds.contains(Coordinate3::new(0, 3, -1)));
+        let grown = bounds.grow(1);
+        assert!(grown.contains(Coordinate3::new(0, 3, -1)));
+        assert_eq!(grown.volume(), 5 * 6 * 5);
+        assert_eq!(Bounds3::<i32>::from_coordinates([]), None);
+    }
+}
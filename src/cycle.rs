@@ -0,0 +1,115 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::PrimInt;
+
+// A simulation that eventually repeats itself. Step n is the state after
+// stepping n times from the initial one, and steps start..start + length
+// repeat forever after.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<M> {
+    pub start: usize,
+    pub length: usize,
+    // the measurement at every step up to the first repeat
+    history: Vec<M>,
+}
+
+// Step a simulation until a state key repeats, measuring something like a
+// height or score at each step. The key needs to capture everything that
+// decides the future, e.g. the surface of a pile rather than the whole pile.
+pub fn find_cycle<S, K, F, G, H, M>(
+    mut state: S,
+    mut step: F,
+    mut key: G,
+    mut measure: H,
+) -> Cycle<M>
+where
+    K: Eq + Hash,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+    H: FnMut(&S) -> M,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    loop {
+        let now = history.len();
+        if let Some(start) = seen.insert(key(&state), now) {
+            history.push(measure(&state));
+            return Cycle {
+                start,
+                length: now - start,
+                history,
+            };
+        }
+        history.push(measure(&state));
+        step(&mut state);
+    }
+}
+
+impl<M: PrimInt> Cycle<M> {
+    // The measurement after any number of steps, assuming it grows by the same
+    // amount every time round the cycle
+    pub fn extrapolate(&self, steps: u64) -> M {
+        let steps = steps as usize;
+        if let Some(&value) = self.history.get(steps) {
+            return value;
+        }
+        let into_cycle = steps - self.start;
+        let (repeats, offset) = (into_cycle / self.length, into_cycle % self.length);
+        let growth = self.history[self.start + self.length] - self.history[self.start];
+        self.history[self.start + offset] + growth * M::from(repeats).unwrap()
+    }
+}
+
+impl<M: Copy> Cycle<M> {
+    // The state key repeats, so does whatever was measured
+    pub fn at(&self, steps: u64) -> M {
+        let steps = steps as usize;
+        if steps < self.start {
+            self.history[steps]
+        } else {
+            self.history[self.start + (steps - self.start) % self.length]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_cycle() {
+        // 0, 1, 2, then 3, 4, 5, 6 forever
+        let cycle = find_cycle(0, |n| *n = if *n == 6 { 3 } else { *n + 1 }, |&n| n, |&n| n);
+        assert_eq!((cycle.start, cycle.length), (3, 4));
+        assert_eq!(cycle.at(2), 2);
+        assert_eq!(cycle.at(6), 6);
+        assert_eq!(cycle.at(7), 3);
+        assert_eq!(cycle.at(1_000_000_005), 5);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // a counter that goes up by 1 then 2 alternately after a slow start,
+        // keyed only by the part that repeats
+        let steps = [1, 0, 0, 1, 2];
+        let cycle = find_cycle(
+            (0usize, 0u64),
+            |(i, total)| {
+                *total += steps[*i];
+                *i = if *i == 4 { 3 } else { *i + 1 };
+            },
+            |&(i, _)| i,
+            |&(_, total)| total,
+        );
+        assert_eq!((cycle.start, cycle.length), (3, 2));
+        // step by step that's 0, 1, 1, 1, 2, 4, 5, 7, 8...
+        let expected = [0, 1, 1, 1, 2, 4, 5, 7, 8, 10, 11];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!(cycle.extrapolate(n as u64), value);
+        }
+        assert_eq!(
+            cycle.extrapolate(1_000_000_000_001),
+            1 + 3 * 499_999_999_999
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use itertools::Itertools;
+
+use crate::{
+    parsers::{blocks, integer, lines, run},
+    Solution,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input<'a> = Vec<i32>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<i32>> {
+    let groups = run(blocks(lines(integer::<i32>)), input)?;
+    Ok(groups.iter().map(|group| group.iter().sum()).collect())
+}
+
+pub fn solve_part1(input: &[i32]) -> i32 {
+    *input.iter().max().unwrap()
+}
+
+pub fn solve_part2(input: &[i32]) -> i32 {
+    input.iter().sorted().rev().take(3).sum()
+}
@@ -1,9 +1,8 @@
-use advent_of_code2022::{print_coordinates, Coordinate};
-use itertools::Itertools;
+use crate::{ocr, parsers::each_line, Coordinate, Solution};
 use reformation::Reformation;
 
 #[derive(Reformation, Debug, Clone)]
-enum Instruction {
+pub enum Instruction {
     #[reformation("addx {}")]
     Add(i32),
     #[reformation("noop")]
@@ -12,22 +11,33 @@ enum Instruction {
 
 const WIDTH: i32 = 40;
 
-fn main() -> color_eyre::Result<()> {
-    let input = include_str!("../../input/day10.txt");
-    let instructions = parse_input(input);
-    println!("{}", solve_part1(&instructions));
-    print_coordinates(&solve_part2(&instructions), true);
-    Ok(())
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input<'a> = Vec<Instruction>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<String> {
+        Ok(ocr::read(solve_part2(input))?)
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|line| Instruction::parse(line).unwrap())
-        .collect_vec()
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Instruction>> {
+    each_line(input, "\"addx N\" or \"noop\"", Instruction::parse)
 }
 
-fn solve_part1(input: &[Instruction]) -> i32 {
+pub fn solve_part1(input: &[Instruction]) -> i32 {
     let mut cycle = 1;
     let mut register = 1;
     let mut result = 0;
@@ -62,7 +72,7 @@ struct State {
     register: i32,
 }
 
-fn solve_part2(input: &[Instruction]) -> Vec<Coordinate<i32>> {
+pub fn solve_part2(input: &[Instruction]) -> Vec<Coordinate<i32>> {
     input
         .iter()
         .fold(
@@ -98,10 +108,10 @@ fn solve_part2(input: &[Instruction]) -> Vec<Coordinate<i32>> {
 }
 
 fn draw_pixel(cycle: i32, register: i32) -> Option<Coordinate<i32>> {
-    let row = cycle.div_euclid(WIDTH);
+    let row = (cycle - 1).div_euclid(WIDTH);
     let col = (cycle - 1) % WIDTH;
 
-    let active = vec![register - 1, register, register + 1];
+    let active = [register - 1, register, register + 1];
     if active.contains(&col) {
         Some(Coordinate { row, col })
     } else {
@@ -112,20 +122,31 @@ fn draw_pixel(cycle: i32, register: i32) -> Option<Coordinate<i32>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Origin, SparseGrid};
 
     #[test]
-    fn test_part1() {
+    fn test_part1() -> color_eyre::Result<()> {
         let input = include_str!("../../input/day10.test.txt");
-        let result = solve_part1(&parse_input(input));
+        let result = solve_part1(&parse_input(input)?);
         dbg!(result);
         assert!(result == 13140);
+        Ok(())
     }
 
     #[test]
-    fn test_part2() {
+    fn test_part2() -> color_eyre::Result<()> {
         let input = include_str!("../../input/day10.test.txt");
-        let result = solve_part2(&parse_input(input));
-        dbg!(&result);
-        print_coordinates(&result, true);
+        let result = solve_part2(&parse_input(input)?);
+        let pixels: SparseGrid<i32, ()> = result.into_iter().collect();
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+        assert_eq!(pixels.render_occupied(Origin::TopLeft), expected);
+        Ok(())
     }
 }
@@ -1,11 +1,23 @@
 use std::collections::{HashMap, VecDeque};
 
-use color_eyre::eyre::ContextCompat;
 use itertools::Itertools;
-use reformation::Reformation;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending},
+    combinator::{map, value},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use crate::{
+    parsers::{blocks, integer, run, IResult},
+    Solution,
+};
 
 #[derive(Clone, Debug)]
-struct Monkey {
+pub struct Monkey {
     items: VecDeque<u128>,
     operation: Operation,
     test: u128,
@@ -13,71 +25,132 @@ struct Monkey {
     target_false: usize,
 }
 
-#[derive(Reformation, Clone, Copy, Debug)]
-#[reformation("  Operation: new = {left} {operator} {right}")]
+#[derive(Clone, Copy, Debug)]
 struct Operation {
     left: Operand,
     operator: Operator,
     right: Operand,
 }
 
-#[derive(Reformation, Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug)]
 enum Operand {
-    #[reformation("old")]
     Old,
-    #[reformation(r"{}")]
     Constant(u128),
 }
 
-#[derive(Reformation, Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug)]
 enum Operator {
-    #[reformation(r"\+")]
     Add,
-    #[reformation(r"\*")]
     Multiply,
 }
 
-fn main() -> color_eyre::Result<()> {
-    let input = include_str!("../../input/day11.txt");
-    let input_processed = parse_input(input)?;
-    println!("Part 1: {}", solve_part1(&input_processed));
-    println!("Part 2: {}", solve_part2(&input_processed));
-    Ok(())
-}
-
-fn parse_input(input: &str) -> color_eyre::Result<Vec<Monkey>> {
-    input
-        .split("\n\n")
-        .map(|group| {
-            // skip the number
-            let mut lines = group.lines().skip(1);
-            let items = parse_items(lines.next()?);
-            let operation = Operation::parse(lines.next()?).unwrap();
-            let test = lines.next()?.split(' ').last()?.parse::<u128>().unwrap();
-            let target_true = lines.next()?.chars().last()?.to_digit(10)? as usize;
-            let target_false = lines.next()?.chars().last()?.to_digit(10)? as usize;
-            Some(Monkey {
-                items,
-                operation,
-                test,
-                target_true,
-                target_false,
-            })
-        })
-        .map(|result| result.wrap_err("Failed parsing"))
-        .collect()
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input<'a> = Vec<Monkey>;
+    type Params = ();
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u128> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u128> {
+        Ok(solve_part2(input))
+    }
+}
+
+fn parse_operand(input: &str) -> IResult<&str, Operand> {
+    context(
+        "old or a number",
+        alt((
+            value(Operand::Old, tag("old")),
+            map(integer, Operand::Constant),
+        )),
+    )(input)
+}
+
+fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    let operator = context(
+        "+ or *",
+        alt((
+            value(Operator::Add, char('+')),
+            value(Operator::Multiply, char('*')),
+        )),
+    );
+    map(
+        preceded(
+            context("\"  Operation: new = \"", tag("  Operation: new = ")),
+            tuple((
+                parse_operand,
+                delimited(char(' '), operator, char(' ')),
+                parse_operand,
+            )),
+        ),
+        |(left, operator, right)| Operation {
+            left,
+            operator,
+            right,
+        },
+    )(input)
+}
+
+fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
+    map(
+        tuple((
+            delimited(
+                context("\"Monkey \"", tag("Monkey ")),
+                integer::<usize>,
+                pair(char(':'), line_ending),
+            ),
+            delimited(
+                context("\"  Starting items: \"", tag("  Starting items: ")),
+                separated_list1(tag(", "), integer),
+                line_ending,
+            ),
+            terminated(parse_operation, line_ending),
+            delimited(
+                context("\"  Test: divisible by \"", tag("  Test: divisible by ")),
+                integer,
+                line_ending,
+            ),
+            delimited(
+                context(
+                    "\"    If true: throw to monkey \"",
+                    tag("    If true: throw to monkey "),
+                ),
+                integer,
+                line_ending,
+            ),
+            preceded(
+                context(
+                    "\"    If false: throw to monkey \"",
+                    tag("    If false: throw to monkey "),
+                ),
+                integer,
+            ),
+        )),
+        |(_, items, operation, test, target_true, target_false)| Monkey {
+            items: items.into_iter().collect(),
+            operation,
+            test,
+            target_true,
+            target_false,
+        },
+    )(input)
 }
 
-fn parse_items(line: &str) -> VecDeque<u128> {
-    line.split(':')
-        .last()
-        .unwrap()
-        .split(',')
-        .map(|x| x.trim().parse::<u128>().unwrap())
-        .collect()
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Monkey>> {
+    run(blocks(parse_monkey), input)
 }
 
-fn solve_part1(input: &[Monkey]) -> u128 {
+pub fn solve_part1(input: &[Monkey]) -> u128 {
     let mut monkeys = input.to_vec();
     let gcd = gcd(&monkeys);
     let num_rounds = 20;
@@ -98,7 +171,7 @@ fn process_round(monkeys: &[Monkey], part: u32, gcd: u128) -> (Vec<Monkey>, Vec<
     let mut new_monkeys = monkeys.to_vec();
     let mut inspection_count = vec![0; monkeys.len()];
     for index in 0..monkeys.len() {
-        let (updates, inspections) = process_monkey(&monkeys[index], part, gcd);
+        let (updates, inspections) = process_monkey(&new_monkeys[index], part, gcd);
         inspection_count[index] += inspections;
         new_monkeys = new_monkeys
             .into_iter()
@@ -147,7 +220,7 @@ fn process_monkey(monkey: &Monkey, part: u32, gcd: u128) -> (HashMap<usize, VecD
 }
 
 fn test_worry(worry: &u128, test_value: u128) -> bool {
-    worry % test_value == 0
+    worry.is_multiple_of(test_value)
 }
 
 fn reduce_below_gcd(worry: u128, gcd: u128) -> u128 {
@@ -177,7 +250,7 @@ fn reduce_worry(worry: u128) -> u128 {
     worry.div_euclid(3)
 }
 
-fn solve_part2(input: &[Monkey]) -> u128 {
+pub fn solve_part2(input: &[Monkey]) -> u128 {
     let mut monkeys = input.to_vec();
     let gcd = gcd(&monkeys);
     let num_rounds = 10000;
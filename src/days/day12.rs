@@ -0,0 +1,99 @@
+use crate::{search, Coordinate, Grid, Solution};
+use color_eyre::eyre::ContextCompat;
+
+#[derive(Debug)]
+pub struct Input {
+    start: Coordinate<usize>,
+    end: Coordinate<usize>,
+    grid: Grid<u32>,
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input<'a> = Input;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        solve_part1(input).wrap_err("No path to the end")
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        solve_part2(input).wrap_err("No path to the end")
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Input> {
+    let chars = Grid::parse(input, "a square", Some)?;
+    let start = chars.position(|&c| c == 'S').wrap_err("No start marked")?;
+    let end = chars.position(|&c| c == 'E').wrap_err("No end marked")?;
+    let grid = Grid::parse(input, "a height from a to z, S or E", |c| match c {
+        'S' => Some(0),
+        'E' => Some(25),
+        'a'..='z' => Some((c as u32) - 'a' as u32),
+        _ => None,
+    })?;
+    Ok(Input { start, end, grid })
+}
+
+pub fn solve_part1(input: &Input) -> Option<usize> {
+    find_shortest_path([input.start], input.end, &input.grid)
+}
+
+pub fn solve_part2(input: &Input) -> Option<usize> {
+    let starts = input.grid.coordinates().filter(|&c| input.grid[c] == 0);
+    find_shortest_path(starts, input.end, &input.grid)
+}
+
+// Steps from whichever start is closest
+fn find_shortest_path<I>(starts: I, end: Coordinate<usize>, grid: &Grid<u32>) -> Option<usize>
+where
+    I: IntoIterator<Item = Coordinate<usize>>,
+{
+    search::bfs(
+        starts,
+        |&current| {
+            grid.neighbours(current)
+                .into_iter()
+                .filter(move |&point| valid_move(grid[current], grid[point]))
+        },
+        |&point| point == end,
+    )
+    .map(|path| path.cost)
+}
+
+fn valid_move(current: u32, next: u32) -> bool {
+    next <= (current + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = include_str!("../../input/day12.test.txt");
+        let input_processed = parse_input(input)?;
+        let result = solve_part1(&input_processed).unwrap();
+        dbg!(result);
+        assert!(result == 31);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = include_str!("../../input/day12.test.txt");
+        let input_processed = parse_input(input)?;
+        let result = solve_part2(&input_processed).unwrap();
+        dbg!(result);
+        assert!(result == 29);
+        Ok(())
+    }
+}
@@ -1,57 +1,76 @@
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::{all_consuming, map},
+    character::complete::line_ending,
+    combinator::map,
+    error::context,
     multi::separated_list0,
-    sequence::delimited,
-    Finish, IResult,
+    sequence::{delimited, separated_pair},
 };
 
-type Pair = (Value, Value);
+use crate::{
+    parsers::{blocks, integer, run, IResult},
+    Solution,
+};
+
+pub type Pair = (Value, Value);
 
 #[derive(Debug, Clone)]
-enum Value {
+pub enum Value {
     Number(u32),
     List(Vec<Value>),
 }
 
-fn main() -> color_eyre::Result<()> {
-    let input = parse_input_1(include_str!("../../input/day13.txt"));
-    println!("Part 1: {}", solve_part1(&input));
-    let input_2 = parse_input_2(include_str!("../../input/day13.txt"));
-    println!("Part 2: {}", solve_part2(input_2));
-    Ok(())
-}
-
 fn parse_number(input: &str) -> IResult<&str, Value> {
-    map(nom::character::complete::u32, Value::Number)(input)
+    map(integer, Value::Number)(input)
 }
 
 fn parse_list(input: &str) -> IResult<&str, Value> {
     map(
-        delimited(tag("["), separated_list0(tag(","), parse_value), tag("]")),
+        delimited(
+            tag("["),
+            separated_list0(tag(","), parse_value),
+            context("\",\" or \"]\"", tag("]")),
+        ),
         Value::List,
     )(input)
 }
 
 fn parse_value(input: &str) -> IResult<&str, Value> {
-    alt((parse_list, parse_number))(input)
+    context("a list or a number", alt((parse_list, parse_number)))(input)
 }
 
-fn parse_input_1(input: &str) -> Vec<Pair> {
-    input
-        .split("\n\n")
-        .map(|group| {
-            let (first, last) = group.split_once('\n').unwrap();
-            (
-                all_consuming(parse_value)(first).finish().unwrap().1,
-                all_consuming(parse_value)(last).finish().unwrap().1,
-            )
-        })
-        .collect()
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input<'a> = Vec<Pair>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Pair>> {
+    run(
+        blocks(separated_pair(parse_value, line_ending, parse_value)),
+        input,
+    )
 }
 
-fn solve_part1(input: &[Pair]) -> usize {
+pub fn solve_part1(input: &[Pair]) -> usize {
     let result = input
         .iter()
         .enumerate()
@@ -87,15 +106,12 @@ fn validate_pair(left: &Value, right: &Value) -> std::cmp::Ordering {
     }
 }
 
-fn parse_input_2(input: &str) -> Vec<Value> {
-    input
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| all_consuming(parse_value)(line).finish().unwrap().1)
-        .collect()
-}
-
-fn solve_part2(mut input: Vec<Value>) -> usize {
+pub fn solve_part2(input: &[Pair]) -> usize {
+    // part 2 ignores the pairing and sorts every packet
+    let mut input = input
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
+        .collect_vec();
     // divider packets
     input.push(Value::List(vec![Value::List(vec![Value::Number(2)])]));
     input.push(Value::List(vec![Value::List(vec![Value::Number(6)])]));
@@ -104,22 +120,12 @@ fn solve_part2(mut input: Vec<Value>) -> usize {
         .iter()
         .enumerate()
         .filter(|(_, value)| match value {
-            Value::List(child) => {
-                if child.len() == 1 {
-                    match &child[0] {
-                        Value::List(grandchild) => {
-                            if grandchild.len() == 1 {
-                                matches!(&grandchild[0], Value::Number(2) | Value::Number(6))
-                            } else {
-                                false
-                            }
-                        }
-                        _ => false,
-                    }
-                } else {
-                    false
+            Value::List(child) if child.len() == 1 => match &child[0] {
+                Value::List(grandchild) if grandchild.len() == 1 => {
+                    matches!(&grandchild[0], Value::Number(2) | Value::Number(6))
                 }
-            }
+                _ => false,
+            },
             _ => false,
         })
         .map(|(index, _)| index + 1)
@@ -132,7 +138,7 @@ mod tests {
 
     #[test]
     fn test_part1() -> color_eyre::Result<()> {
-        let input = parse_input_1(include_str!("../../input/day13.test.txt"));
+        let input = parse_input(include_str!("../../input/day13.test.txt"))?;
         let result = solve_part1(&input);
         assert_eq!(result, 13);
         Ok(())
@@ -140,8 +146,8 @@ mod tests {
 
     #[test]
     fn test_part2() -> color_eyre::Result<()> {
-        let input = parse_input_2(include_str!("../../input/day13.test.txt"));
-        let result = solve_part2(input);
+        let input = parse_input(include_str!("../../input/day13.test.txt"))?;
+        let result = solve_part2(&input);
         assert_eq!(result, 140);
         Ok(())
     }
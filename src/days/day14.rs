@@ -0,0 +1,250 @@
+use crate::{
+    parsers::{lines, run, xy, IResult},
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Coordinate, Origin, Part, Solution, SparseGrid,
+};
+use itertools::Itertools;
+use nom::{bytes::complete::tag, multi::separated_list1};
+
+type Input = Vec<Coordinate<u32>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Contents {
+    Rock,
+    Sand,
+    // only ever drawn, never in the grid
+    Source,
+}
+
+const SAND_ORIGIN: Coordinate<u32> = Coordinate { row: 0, col: 500 };
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input<'a> = Vec<Input>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part2(input))
+    }
+
+    fn record(
+        input: &Self::Input<'_>,
+        _params: &(),
+        part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        Ok(Some(
+            recorder.record(&mut Cave::new(input, part == Part::Two)),
+        ))
+    }
+}
+
+fn parse_line(input: &str) -> IResult<&str, Vec<Coordinate<u32>>> {
+    separated_list1(tag(" -> "), xy)(input)
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
+    run(lines(parse_line), input)
+}
+
+fn build_grid(input: &Vec<Input>) -> SparseGrid<u32, Contents> {
+    let mut map = SparseGrid::new();
+    for line in input {
+        for (start, end) in line.iter().tuple_windows() {
+            // every point between the points is rock
+            let coords = start.get_between(end);
+            for c in coords {
+                map.insert(c, Contents::Rock);
+            }
+        }
+    }
+    map
+}
+
+fn drop_sand(grid: &SparseGrid<u32, Contents>, lowest: u32) -> Option<Coordinate<u32>> {
+    let mut current = SAND_ORIGIN;
+    loop {
+        if current.row > lowest {
+            return None;
+        }
+        let contents = grid.get(current);
+        match contents {
+            Some(_) => {
+                // there's something in the way
+                let left_coord = Coordinate {
+                    row: current.row,
+                    col: current.col - 1,
+                };
+                let left = grid.get(left_coord);
+                if left.is_none() {
+                    current = left_coord;
+                } else {
+                    let right_coord = Coordinate {
+                        row: current.row,
+                        col: current.col + 1,
+                    };
+                    let right = grid.get(right_coord);
+                    if right.is_none() {
+                        current = right_coord;
+                    } else {
+                        // we stop one row above
+                        return Some(Coordinate {
+                            row: current.row - 1,
+                            col: current.col,
+                        });
+                    }
+                }
+            }
+            None => {
+                current = Coordinate {
+                    row: current.row + 1,
+                    ..current
+                };
+            }
+        }
+    }
+}
+
+// The cave one grain of sand at a time, see Simulation
+struct Cave {
+    grid: SparseGrid<u32, Contents>,
+    // sand that gets below this has fallen off the map
+    lowest: u32,
+    // sand has come to rest on the source
+    full: bool,
+}
+
+impl Cave {
+    fn new(input: &Vec<Input>, floor: bool) -> Cave {
+        let mut grid = build_grid(input);
+        let mut lowest = grid.bounds().unwrap().max.row;
+        if floor {
+            lowest += 2;
+            // widest shape is a triangle
+            let width = 2 * lowest + 1;
+            for col in (SAND_ORIGIN.col - width / 2)..=(SAND_ORIGIN.col + width / 2) {
+                grid.insert(Coordinate { row: lowest, col }, Contents::Rock);
+            }
+        }
+        Cave {
+            grid,
+            lowest,
+            full: false,
+        }
+    }
+}
+
+impl Simulation for Cave {
+    type Kind = Contents;
+
+    // Drop one grain, unless it would fall forever or there's no room left
+    fn step(&mut self) -> bool {
+        if self.full {
+            return false;
+        }
+        match drop_sand(&self.grid, self.lowest) {
+            Some(c) => {
+                self.grid.insert(c, Contents::Sand);
+                self.full = c == SAND_ORIGIN;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn picture(&self) -> Picture<Contents> {
+        let cells = self.grid.iter().map(|(c, &contents)| (c, contents));
+        let source = (!self.full).then_some((SAND_ORIGIN, Contents::Source));
+        Picture::from_cells(cells.chain(source), Origin::TopLeft)
+    }
+
+    fn legend(&self) -> Legend<Contents> {
+        Legend::new(Style::new('.', Rgb::WHITE, "air"))
+            .with(Contents::Rock, Style::new('#', Rgb(96, 96, 96), "rock"))
+            .with(Contents::Sand, Style::new('o', Rgb(220, 180, 80), "sand"))
+            .with(
+                Contents::Source,
+                Style::new('+', Rgb(200, 60, 40), "source of the sand"),
+            )
+    }
+}
+
+pub fn solve_part1(input: &Vec<Input>) -> usize {
+    pour(Cave::new(input, false))
+}
+
+fn pour(mut cave: Cave) -> usize {
+    while cave.step() {}
+    count_grains(&cave.grid)
+}
+
+fn count_grains(grid: &SparseGrid<u32, Contents>) -> usize {
+    grid.iter()
+        .filter(|(_, v)| matches!(v, Contents::Sand))
+        .count()
+}
+
+pub fn solve_part2(input: &Vec<Input>) -> usize {
+    pour(Cave::new(input, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day14.test.txt"))?;
+        let result = solve_part1(&input);
+        assert_eq!(result, 24);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day14.test.txt"))?;
+        let result = solve_part2(&input);
+        assert_eq!(result, 93);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day14.test.txt"))?;
+        let recorder = Recorder {
+            every: 24,
+            ..Recorder::default()
+        };
+        let recording = recorder.capture(&mut Cave::new(&input, false));
+        let last = recording.frames.last().unwrap();
+        assert_eq!(last.step, 24);
+        assert_eq!(
+            last.picture.to_text(&recording.legend),
+            "......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+"
+        );
+        Ok(())
+    }
+}
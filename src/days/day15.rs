@@ -0,0 +1,202 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use crate::{
+    parse_param,
+    parsers::{integer, lines, run, IResult},
+    Coordinate, IntervalSet, Solution,
+};
+use color_eyre::eyre::{bail, eyre};
+use nom::{
+    bytes::complete::{tag, take},
+    combinator::map,
+    error::context,
+    sequence::{pair, preceded, separated_pair},
+};
+use num::abs;
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    sensor: Coordinate<i32>,
+    beacon: Coordinate<i32>,
+    distance: i32,
+}
+
+// part 1 scans a single row, part 2 searches 0..=max_bound on both axes
+pub struct Params {
+    pub row: i32,
+    pub max_bound: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            row: 2_000_000,
+            max_bound: 4_000_000,
+        }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input<'a> = Vec<Input>;
+    type Params = Params;
+    type Part1 = u128;
+    type Part2 = i64;
+
+    fn example_params() -> Params {
+        Params {
+            row: 10,
+            max_bound: 20,
+        }
+    }
+
+    fn set_param(params: &mut Params, name: &str, value: &str) -> color_eyre::Result<()> {
+        match name {
+            "row" => params.row = parse_param(name, value)?,
+            "max_bound" => params.max_bound = parse_param(name, value)?,
+            _ => bail!("Day 15 has no parameter {}", name),
+        }
+        Ok(())
+    }
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, params: &Self::Params) -> color_eyre::Result<u128> {
+        Ok(solve_part1(input, params.row))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, params: &Self::Params) -> color_eyre::Result<i64> {
+        solve_part2(input, params.max_bound)
+    }
+}
+
+fn parse_assignment(input: &str) -> IResult<&str, i32> {
+    preceded(take(2u32), integer)(input)
+}
+
+fn parse_coordinate(input: &str) -> IResult<&str, Coordinate<i32>> {
+    map(
+        separated_pair(
+            parse_assignment,
+            context("\", \"", tag(", ")),
+            parse_assignment,
+        ),
+        |(x, y)| Coordinate { row: y, col: x },
+    )(input)
+}
+
+fn parse_sensor(input: &str) -> IResult<&str, Coordinate<i32>> {
+    preceded(
+        context("\"Sensor at \"", tag("Sensor at ")),
+        parse_coordinate,
+    )(input)
+}
+
+fn parse_beacon(input: &str) -> IResult<&str, Coordinate<i32>> {
+    preceded(
+        context(
+            "\": closest beacon is at \"",
+            tag(": closest beacon is at "),
+        ),
+        parse_coordinate,
+    )(input)
+}
+
+fn parse_line(input: &str) -> IResult<&str, Input> {
+    map(pair(parse_sensor, parse_beacon), |(sensor, beacon)| Input {
+        sensor,
+        beacon,
+        distance: sensor.manhattan(&beacon),
+    })(input)
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
+    run(lines(parse_line), input)
+}
+
+pub fn solve_part1(input: &[Input], row: i32) -> u128 {
+    let covered = row_coverage(input, row);
+    let beacons = input
+        .iter()
+        .map(|i| i.beacon)
+        .filter(|beacon| beacon.row == row && covered.contains(beacon.col))
+        .collect::<HashSet<_>>();
+    covered.len() - beacons.len() as u128
+}
+
+pub fn solve_part2(input: &[Input], max_bound: i32) -> color_eyre::Result<i64> {
+    let (row, col) = (0..=max_bound)
+        .find_map(|row| {
+            let gap = row_coverage(input, row).gaps(0..=max_bound).next()?;
+            Some((row, *gap.start()))
+        })
+        .ok_or_else(|| eyre!("Every position up to {} is covered", max_bound))?;
+    log::debug!("Distress beacon at x={}, y={}", col, row);
+    Ok((col as i64) * 4000000 + (row as i64))
+}
+
+// The columns on a row that are closer to some sensor than its beacon
+fn row_coverage(input: &[Input], row: i32) -> IntervalSet<i32> {
+    input.iter().filter_map(|i| sensor_range(i, row)).collect()
+}
+
+// Each sensor covers a diamond, so a shrinking range either side of its row
+fn sensor_range(input: &Input, row: i32) -> Option<RangeInclusive<i32>> {
+    let spare = input.distance - abs(input.sensor.row - row);
+    if spare < 0 {
+        return None;
+    }
+    Some((input.sensor.col - spare)..=(input.sensor.col + spare))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day15.test.txt"))?;
+        let result = solve_part1(&input, 10);
+        assert_eq!(result, 26);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day15.test.txt"))?;
+        let result = solve_part2(&input, 20)?;
+        assert_eq!(result, 56000011);
+        let input = parse_input("Sensor at x=0, y=0: closest beacon is at x=100, y=0\n")?;
+        assert!(solve_part2(&input, 20).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sensor_range() {
+        let sensor = Input {
+            sensor: Coordinate { row: 2, col: 2 },
+            beacon: Coordinate { row: 2, col: 2 },
+            distance: 2,
+        };
+        let ranges = (-1..=5)
+            .map(|row| sensor_range(&sensor, row))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                None,
+                Some(2..=2),
+                Some(1..=3),
+                Some(0..=4),
+                Some(1..=3),
+                Some(2..=2),
+                None
+            ]
+        );
+    }
+}
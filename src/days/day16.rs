@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::{ensure, eyre, ContextCompat};
+use itertools::Itertools;
+use petgraph::{prelude::UnGraph, stable_graph::NodeIndex, Graph, Undirected};
+
+use crate::{Memo, Solution};
+
+pub struct Input<'a> {
+    name: &'a str,
+    flow: u32,
+    connected: Vec<&'a str>,
+}
+
+pub struct Valve {
+    flow: u32,
+    // which bit of OpenValves is this valve's, for those worth opening
+    bit: Option<u32>,
+}
+
+// A bit is set once its valve is open
+type OpenValves = u64;
+type State = (NodeIndex<u32>, OpenValves, u32);
+type State2 = (NodeIndex<u32>, NodeIndex<u32>, OpenValves, u32);
+
+#[derive(PartialEq, Eq, Debug)]
+enum Decision {
+    Valve(NodeIndex<u32>),
+    Move(NodeIndex<u32>),
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input<'a> = (NodeIndex<u32>, UnGraph<Valve, u32>);
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        build_graph(parsing::parse_input(input)?)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part1(&input.0, &input.1))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part2(&input.0, &input.1))
+    }
+}
+
+pub mod parsing {
+    use nom::{
+        branch::alt,
+        bytes::complete::{tag, take},
+        combinator::map,
+        error::context,
+        multi::separated_list1,
+        sequence::{preceded, tuple},
+    };
+
+    use super::Input;
+    use crate::parsers::{lines, run, IResult};
+
+    fn parse_name(input: &str) -> IResult<&str, &str> {
+        preceded(
+            context("\"Valve \"", tag("Valve ")),
+            context("a two letter valve name", take(2u32)),
+        )(input)
+    }
+
+    fn parse_flow(input: &str) -> IResult<&str, u32> {
+        preceded(
+            context("\" has flow rate=\"", tag(" has flow rate=")),
+            context("a flow rate", nom::character::complete::u32),
+        )(input)
+    }
+
+    fn parse_connected(input: &str) -> IResult<&str, Vec<&str>> {
+        preceded(
+            context(
+                "\"; tunnels lead to valves \"",
+                alt((
+                    tag("; tunnels lead to valves "),
+                    tag("; tunnel leads to valve "),
+                )),
+            ),
+            separated_list1(tag(", "), take(2u32)),
+        )(input)
+    }
+
+    fn parse_line(input: &str) -> IResult<&str, Input<'_>> {
+        map(
+            tuple((parse_name, parse_flow, parse_connected)),
+            |(name, flow, connected)| Input {
+                name,
+                flow,
+                connected,
+            },
+        )(input)
+    }
+
+    pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input<'_>>> {
+        run(lines(parse_line), input)
+    }
+}
+
+pub fn build_graph(nodes: Vec<Input>) -> color_eyre::Result<(NodeIndex<u32>, UnGraph<Valve, u32>)> {
+    let flowing = nodes.iter().filter(|input| input.flow > 0).count();
+    ensure!(
+        flowing <= 64,
+        "Found {} valves with a flow, open valves are tracked as bits of a u64 so at most 64 fit",
+        flowing
+    );
+    let mut bits = 0..;
+    // first add nodes
+    let mut root = None;
+    let (mut g, map) = nodes.iter().fold(
+        (
+            Graph::<Valve, u32, Undirected>::new_undirected(),
+            HashMap::new(),
+        ),
+        |(mut g, mut map), input| {
+            let node_index = g.add_node(Valve {
+                flow: input.flow,
+                bit: (input.flow > 0).then(|| bits.next().unwrap()),
+            });
+            if input.name == "AA" {
+                root = Some(node_index);
+            }
+            map.insert(input.name, node_index);
+            (g, map)
+        },
+    );
+    let root = root.wrap_err("There is no valve AA to start from")?;
+    // now add edges. This avoids having to maybe add nodes
+    for input in &nodes {
+        let source_index = map[input.name];
+        for target in &input.connected {
+            let target_index = map.get(target).ok_or_else(|| {
+                eyre!(
+                    "Valve {} has a tunnel to unknown valve {}",
+                    input.name,
+                    target
+                )
+            })?;
+            g.update_edge(source_index, *target_index, 1);
+        }
+    }
+    Ok((root, g))
+}
+
+pub fn solve_part1(root: &NodeIndex<u32>, input: &UnGraph<Valve, u32>) -> u32 {
+    // do graph search things
+    let mut memo = Memo::new(|memo, state: &State| find_max_pressure(input, memo, *state));
+    let result = memo.get((*root, 0, 1));
+    log::debug!("Day 16 part 1 cache: {}", memo.stats());
+    result
+}
+
+fn open(graph: &UnGraph<Valve, u32>, open_valves: OpenValves, valve: NodeIndex<u32>) -> OpenValves {
+    match graph[valve].bit {
+        Some(bit) => open_valves | 1 << bit,
+        None => open_valves,
+    }
+}
+
+fn is_open(open_valves: OpenValves, valve: &Valve) -> bool {
+    valve.bit.is_some_and(|bit| open_valves & 1 << bit != 0)
+}
+
+fn get_flow(graph: &UnGraph<Valve, u32>, open_valves: OpenValves) -> u32 {
+    graph
+        .node_weights()
+        .filter(|valve| is_open(open_valves, valve))
+        .map(|valve| valve.flow)
+        .sum()
+}
+
+fn find_max_pressure(
+    graph: &UnGraph<Valve, u32>,
+    memo: &mut Memo<State, u32>,
+    (current, open_valves, time_passed): State,
+) -> u32 {
+    let flow_this_minute = get_flow(graph, open_valves);
+    if time_passed == 30 {
+        // count the last minute of flow
+        return flow_this_minute;
+    }
+    // at every node you have choices
+    let choices = get_choices(graph, &current, open_valves);
+    choices
+        .iter()
+        .map(|c| match c {
+            // try opening this one then moving
+            Decision::Valve(v) => {
+                memo.get((current, open(graph, open_valves, *v), time_passed + 1))
+            }
+            Decision::Move(p) => memo.get((*p, open_valves, time_passed + 1)),
+        })
+        .map(|child| flow_this_minute + child)
+        .max()
+        // nowhere to go, so wait for the time to run out
+        .unwrap_or(flow_this_minute * (31 - time_passed))
+}
+
+pub fn solve_part2(root: &NodeIndex<u32>, input: &UnGraph<Valve, u32>) -> u32 {
+    // do graph search things
+    let mut memo = Memo::new(|memo, state: &State2| find_max_pressure_2(input, memo, *state));
+    // 4 minutes have passed
+    let result = memo.get((*root, *root, 0, 5));
+    log::debug!("Day 16 part 2 cache: {}", memo.stats());
+    result
+}
+
+fn find_max_pressure_2(
+    graph: &UnGraph<Valve, u32>,
+    memo: &mut Memo<State2, u32>,
+    (current, current_elephant, open_valves, time_passed): State2,
+) -> u32 {
+    let flow_this_minute = get_flow(graph, open_valves);
+    if time_passed == 30 {
+        // count the last minute of flow
+        return flow_this_minute;
+    }
+    // at every node you have choices
+    let choices = get_choices(graph, &current, open_valves);
+    let temp = get_choices(graph, &current_elephant, open_valves);
+    let elephant_choices = temp
+        .iter()
+        .filter(|choice| match choice {
+            // make sure they don't open the same valve
+            v @ Decision::Valve(_) => !choices.contains(v),
+            _ => true,
+        })
+        .collect_vec();
+
+    choices
+        .iter()
+        .cartesian_product(elephant_choices)
+        .map(|(c1, c2)| match (c1, c2) {
+            (Decision::Valve(v1), Decision::Valve(v2)) => memo.get((
+                current,
+                current_elephant,
+                open(graph, open(graph, open_valves, *v1), *v2),
+                time_passed + 1,
+            )),
+            (Decision::Valve(v), Decision::Move(p)) => {
+                memo.get((current, *p, open(graph, open_valves, *v), time_passed + 1))
+            }
+            (Decision::Move(p), Decision::Valve(v)) => memo.get((
+                *p,
+                current_elephant,
+                open(graph, open_valves, *v),
+                time_passed + 1,
+            )),
+            (Decision::Move(p1), Decision::Move(p2)) => {
+                memo.get((*p1, *p2, open_valves, time_passed + 1))
+            }
+        })
+        .map(|child| child + flow_this_minute)
+        .max()
+        // nowhere to go, so wait for the time to run out
+        .unwrap_or(flow_this_minute * (31 - time_passed))
+}
+
+fn get_choices(
+    graph: &UnGraph<Valve, u32>,
+    current: &NodeIndex<u32>,
+    open_valves: OpenValves,
+) -> Vec<Decision> {
+    let mut decisions = vec![];
+    // if valve is not open, open valve
+    let valve = &graph[*current];
+    if valve.bit.is_some() && !is_open(open_valves, valve) {
+        // try opening this one then moving
+        decisions.push(Decision::Valve(*current));
+    }
+    // try just moving to adjacent valve
+    decisions.extend(graph.neighbors(*current).map(Decision::Move));
+    decisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // expected behaviour
+    // 00 AA []
+    // 01 DD []
+    // 02 DD [DD]
+    // 03 CC [DD]
+    // 04 BB [DD]
+    // 05 BB [DD, BB]
+    // 06 AA [DD, BB]
+    // 07 II [DD, BB]
+    // 08 JJ [DD, BB]
+    // 09 JJ [DD, BB, JJ]
+    // 10 II [DD, BB, JJ]
+    // 11 AA [DD, BB, JJ]
+    // 12 DD [DD, BB, JJ]
+    // 13 EE [DD, BB, JJ]
+    // 14 FF [DD, BB, JJ]
+    // 15 GG [DD, BB, JJ]
+    // 16 HH [DD, BB, JJ]
+    // 17 HH [DD, BB, JJ, HH]
+    // 18 GG [DD, BB, JJ, HH]
+    // 19 FF [DD, BB, JJ, HH]
+    // 20 EE [DD, BB, JJ, HH]
+    // 21 EE [DD, BB, JJ, HH, EE]
+    // 22 DD [DD, BB, JJ, HH, EE]
+    // 23 CC [DD, BB, JJ, HH, EE]
+    // 24 CC [DD, BB, JJ, HH, EE, CC]
+    // 25 CC [DD, BB, JJ, HH, EE, CC]
+    // 26 CC [DD, BB, JJ, HH, EE, CC]
+    // 27 CC [DD, BB, JJ, HH, EE, CC]
+    // 28 CC [DD, BB, JJ, HH, EE, CC]
+    // 29 CC [DD, BB, JJ, HH, EE, CC]
+    // 30 CC [DD, BB, JJ, HH, EE, CC]
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day16.test.txt"))?;
+        let (root, graph) = build_graph(input)?;
+        let result = solve_part1(&root, &graph);
+        assert_eq!(result, 1651);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day16.test.txt"))?;
+        let (root, graph) = build_graph(input)?;
+        let result = solve_part2(&root, &graph);
+        assert_eq!(result, 1707);
+        Ok(())
+    }
+
+    // A ring of tunnels starting at AA, with the given flow rate at each valve
+    fn tunnels(flows: &[u32]) -> String {
+        let name = |i: usize| {
+            format!(
+                "{}{}",
+                (b'A' + (i / 26) as u8) as char,
+                (b'A' + (i % 26) as u8) as char
+            )
+        };
+        (0..flows.len())
+            .map(|i| {
+                let next = (i + 1) % flows.len();
+                format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}\n",
+                    name(i),
+                    flows[i],
+                    name(next)
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_many_valves() -> color_eyre::Result<()> {
+        // only the valves with a flow take up a bit
+        let mut flows = vec![0; 100];
+        flows[1] = 10;
+        let input = tunnels(&flows);
+        let (root, graph) = build_graph(parsing::parse_input(&input)?)?;
+        // open AB at the end of minute 2
+        assert_eq!(solve_part1(&root, &graph), 280);
+        let input = tunnels(&[1; 65]);
+        assert!(build_graph(parsing::parse_input(&input)?).is_err());
+        Ok(())
+    }
+}
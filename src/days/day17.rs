@@ -1,9 +1,9 @@
-use std::{
-    collections::{HashMap, HashSet},
-    time::Instant,
+use crate::{
+    cycle,
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Bounds, Origin, Part, PosCoordinate, Solution, SparseGrid,
 };
-
-use advent_of_code2022::PosCoordinate;
 use shapes::{Cross, HLine, Rock, ShapeType, Square, VLine, L};
 
 #[derive(Clone, Debug)]
@@ -17,56 +17,69 @@ pub enum Jet {
     Right,
 }
 
+// the top of the pile, the next jet and the next rock decide what happens next
 type History = ((u8, u8, u8), usize, ShapeType);
-//                  height, rocks
-type HistoryValue = (u64, u64);
-
-fn main() -> color_eyre::Result<()> {
-    let input = parsing::parse_input(include_str!("../../input/day17.txt"));
-    let time = Instant::now();
-    println!(
-        "Part 1: {} in {}ms",
-        solve_part1(&input),
-        time.elapsed().as_millis()
-    );
-    let time = Instant::now();
-    println!(
-        "Part 2: {} in {}ms",
-        solve_part2(&input),
-        time.elapsed().as_millis()
-    );
-    Ok(())
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input<'a> = Input;
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u64> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u64> {
+        Ok(solve_part2(input))
+    }
+
+    // Both parts drop the same rocks, part 2 just drops far more than anyone
+    // would want to watch
+    fn record(
+        input: &Self::Input<'_>,
+        _params: &(),
+        _part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        Ok(Some(
+            recorder
+                .or_limit(2022)
+                .record(&mut Chamber::new(&input.jets)),
+        ))
+    }
 }
 
-mod parsing {
+pub mod parsing {
 
     use super::{Input, Jet};
-    use nom::{branch::alt, bytes::complete::tag, combinator::map, multi::many0, Finish, IResult};
+    use crate::parsers::{run, IResult};
+    use nom::{character::complete::one_of, combinator::map, error::context, multi::many1};
 
     fn parse_line(input: &str) -> IResult<&str, Input> {
         map(
-            many0(map(alt((tag("<"), tag(">"))), |c| match c {
-                "<" => Jet::Left,
-                ">" => Jet::Right,
-                _ => unreachable!(""),
+            many1(map(context("a jet (< or >)", one_of("<>")), |c| match c {
+                '<' => Jet::Left,
+                _ => Jet::Right,
             })),
             |jets| Input { jets },
         )(input)
     }
 
-    pub fn parse_input(input: &str) -> Input {
-        input
-            .lines()
-            .next()
-            .map(|line| parse_line(line).finish().unwrap().1)
-            .unwrap()
+    pub fn parse_input(input: &str) -> color_eyre::Result<Input> {
+        run(parse_line, input)
     }
 }
 
 mod shapes {
-    use std::collections::HashSet;
-
-    use advent_of_code2022::PosCoordinate;
+    use crate::{PosCoordinate, SparseGrid};
 
     const MAX_COL: u64 = 8;
 
@@ -85,9 +98,9 @@ mod shapes {
     }
 
     pub trait Shape {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>>;
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>>;
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>>;
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>>;
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>>;
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>>;
         fn get_coordinates(&self) -> Vec<PosCoordinate>;
         fn get_highest(&self) -> PosCoordinate;
     }
@@ -123,12 +136,12 @@ mod shapes {
     }
 
     impl Shape for HLine {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let new_left = PosCoordinate {
                 row: self.left.row,
                 col: self.left.col - 1,
             };
-            if new_left.col == 0 || occupied.contains(&new_left) {
+            if new_left.col == 0 || occupied.contains(new_left) {
                 return None;
             }
             Some(Box::new(HLine {
@@ -139,12 +152,12 @@ mod shapes {
             }))
         }
 
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let new_right = PosCoordinate {
                 row: self.right.row,
                 col: self.right.col + 1,
             };
-            if new_right.col == MAX_COL || occupied.contains(&new_right) {
+            if new_right.col == MAX_COL || occupied.contains(new_right) {
                 return None;
             }
             Some(Box::new(HLine {
@@ -155,7 +168,7 @@ mod shapes {
             }))
         }
 
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let new_self = HLine {
                 left: PosCoordinate {
                     row: self.left.row - 1,
@@ -175,10 +188,10 @@ mod shapes {
                 },
             };
             if new_self.left.row == 0
-                || occupied.contains(&new_self.left)
-                || occupied.contains(&new_self.centre_left)
-                || occupied.contains(&new_self.centre_right)
-                || occupied.contains(&new_self.right)
+                || occupied.contains(new_self.left)
+                || occupied.contains(new_self.centre_left)
+                || occupied.contains(new_self.centre_right)
+                || occupied.contains(new_self.right)
             {
                 return None;
             }
@@ -231,7 +244,7 @@ mod shapes {
     }
 
     impl Shape for Cross {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let left = PosCoordinate {
                 row: self.left.row,
                 col: self.left.col - 1,
@@ -245,9 +258,9 @@ mod shapes {
                 col: self.bottom.col - 1,
             };
             if left.col == 0
-                || occupied.contains(&left)
-                || occupied.contains(&top)
-                || occupied.contains(&bottom)
+                || occupied.contains(left)
+                || occupied.contains(top)
+                || occupied.contains(bottom)
             {
                 return None;
             }
@@ -260,7 +273,7 @@ mod shapes {
             }))
         }
 
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let right = PosCoordinate {
                 row: self.right.row,
                 col: self.right.col + 1,
@@ -274,9 +287,9 @@ mod shapes {
                 col: self.bottom.col + 1,
             };
             if right.col == MAX_COL
-                || occupied.contains(&right)
-                || occupied.contains(&top)
-                || occupied.contains(&bottom)
+                || occupied.contains(right)
+                || occupied.contains(top)
+                || occupied.contains(bottom)
             {
                 return None;
             }
@@ -289,7 +302,7 @@ mod shapes {
             }))
         }
 
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             // check left, bottom, right
             let left = PosCoordinate {
                 row: self.left.row - 1,
@@ -304,9 +317,9 @@ mod shapes {
                 col: self.right.col,
             };
             if bottom.col == 0
-                || occupied.contains(&left)
-                || occupied.contains(&bottom)
-                || occupied.contains(&right)
+                || occupied.contains(left)
+                || occupied.contains(bottom)
+                || occupied.contains(right)
             {
                 return None;
             }
@@ -362,7 +375,7 @@ mod shapes {
     }
 
     impl Shape for L {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let bottom_left = PosCoordinate {
                 row: self.bottom_left.row,
                 col: self.bottom_left.col - 1,
@@ -376,9 +389,9 @@ mod shapes {
                 col: self.centre_right.col - 1,
             };
             if bottom_left.col == 0
-                || occupied.contains(&bottom_left)
-                || occupied.contains(&top_right)
-                || occupied.contains(&centre_right)
+                || occupied.contains(bottom_left)
+                || occupied.contains(top_right)
+                || occupied.contains(centre_right)
             {
                 return None;
             }
@@ -391,7 +404,7 @@ mod shapes {
             }))
         }
 
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let bottom_right = PosCoordinate {
                 row: self.bottom_right.row,
                 col: self.bottom_right.col + 1,
@@ -405,9 +418,9 @@ mod shapes {
                 col: self.centre_right.col + 1,
             };
             if bottom_right.col == MAX_COL
-                || occupied.contains(&bottom_right)
-                || occupied.contains(&top_right)
-                || occupied.contains(&centre_right)
+                || occupied.contains(bottom_right)
+                || occupied.contains(top_right)
+                || occupied.contains(centre_right)
             {
                 return None;
             }
@@ -420,7 +433,7 @@ mod shapes {
             }))
         }
 
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let bottom_right = PosCoordinate {
                 row: self.bottom_right.row - 1,
                 col: self.bottom_right.col,
@@ -434,9 +447,9 @@ mod shapes {
                 col: self.bottom_centre.col,
             };
             if bottom_right.row == 0
-                || occupied.contains(&bottom_right)
-                || occupied.contains(&bottom_left)
-                || occupied.contains(&bottom_centre)
+                || occupied.contains(bottom_right)
+                || occupied.contains(bottom_left)
+                || occupied.contains(bottom_centre)
             {
                 return None;
             }
@@ -493,7 +506,7 @@ mod shapes {
     }
 
     impl Shape for VLine {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let new_self = VLine {
                 bottom: PosCoordinate {
                     row: self.bottom.row,
@@ -513,17 +526,17 @@ mod shapes {
                 },
             };
             if new_self.bottom.col == 0
-                || occupied.contains(&new_self.bottom)
-                || occupied.contains(&new_self.centre_bottom)
-                || occupied.contains(&new_self.centre_top)
-                || occupied.contains(&new_self.top)
+                || occupied.contains(new_self.bottom)
+                || occupied.contains(new_self.centre_bottom)
+                || occupied.contains(new_self.centre_top)
+                || occupied.contains(new_self.top)
             {
                 return None;
             }
             Some(Box::new(new_self))
         }
 
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let new_self = VLine {
                 bottom: PosCoordinate {
                     row: self.bottom.row,
@@ -543,22 +556,22 @@ mod shapes {
                 },
             };
             if new_self.bottom.col == MAX_COL
-                || occupied.contains(&new_self.bottom)
-                || occupied.contains(&new_self.centre_bottom)
-                || occupied.contains(&new_self.centre_top)
-                || occupied.contains(&new_self.top)
+                || occupied.contains(new_self.bottom)
+                || occupied.contains(new_self.centre_bottom)
+                || occupied.contains(new_self.centre_top)
+                || occupied.contains(new_self.top)
             {
                 return None;
             }
             Some(Box::new(new_self))
         }
 
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let bottom = PosCoordinate {
                 row: self.bottom.row - 1,
                 col: self.bottom.col,
             };
-            if bottom.row == 0 || occupied.contains(&bottom) {
+            if bottom.row == 0 || occupied.contains(bottom) {
                 return None;
             }
             Some(Box::new(VLine {
@@ -607,7 +620,7 @@ mod shapes {
     }
 
     impl Shape for Square {
-        fn move_left(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_left(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let top_left = PosCoordinate {
                 row: self.top_left.row,
                 col: self.top_left.col - 1,
@@ -616,8 +629,7 @@ mod shapes {
                 row: self.bottom_left.row,
                 col: self.bottom_left.col - 1,
             };
-            if top_left.col == 0 || occupied.contains(&top_left) || occupied.contains(&bottom_left)
-            {
+            if top_left.col == 0 || occupied.contains(top_left) || occupied.contains(bottom_left) {
                 return None;
             }
             Some(Box::new(Square {
@@ -628,7 +640,7 @@ mod shapes {
             }))
         }
 
-        fn move_right(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn move_right(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let top_right = PosCoordinate {
                 row: self.top_right.row,
                 col: self.top_right.col + 1,
@@ -638,8 +650,8 @@ mod shapes {
                 col: self.bottom_right.col + 1,
             };
             if top_right.col == MAX_COL
-                || occupied.contains(&top_right)
-                || occupied.contains(&bottom_right)
+                || occupied.contains(top_right)
+                || occupied.contains(bottom_right)
             {
                 return None;
             }
@@ -651,7 +663,7 @@ mod shapes {
             }))
         }
 
-        fn descend(&self, occupied: &HashSet<PosCoordinate>) -> Option<Box<dyn Shape>> {
+        fn descend(&self, occupied: &SparseGrid<u64, ()>) -> Option<Box<dyn Shape>> {
             let bottom_right = PosCoordinate {
                 row: self.bottom_right.row - 1,
                 col: self.bottom_right.col,
@@ -661,8 +673,8 @@ mod shapes {
                 col: self.bottom_left.col,
             };
             if bottom_right.row == 0
-                || occupied.contains(&bottom_right)
-                || occupied.contains(&bottom_left)
+                || occupied.contains(bottom_right)
+                || occupied.contains(bottom_left)
             {
                 return None;
             }
@@ -689,8 +701,8 @@ mod shapes {
     }
 }
 
-fn get_next_shape(current: Rock, coordinate: PosCoordinate) -> Rock {
-    match current.shape_type {
+fn get_next_shape(current: ShapeType, coordinate: PosCoordinate) -> Rock {
+    match current {
         ShapeType::HLine => Rock {
             shape: Box::new(Cross::new(coordinate)),
             shape_type: ShapeType::Cross,
@@ -722,98 +734,163 @@ fn get_starting_coordinate(highest_row: u64) -> PosCoordinate {
     }
 }
 
-fn solve_part1(input: &Input) -> u64 {
+pub fn solve_part1(input: &Input) -> u64 {
     solve(input, 2022)
 }
 
-fn solve(input: &Input, rock_count: u64) -> u64 {
-    let jet_cycle = input.jets.len();
-    let mut jet_usage_count = 0;
-    let mut jets = input.jets.iter().cycle();
-    // initial shape
-    let mut shape = Rock {
-        shape: Box::new(HLine::new(PosCoordinate { row: 4, col: 3 })),
-        shape_type: ShapeType::HLine,
-    };
-    let mut highest_row = 0;
-    let mut occupied = HashSet::new();
-    let mut states = HashMap::new();
-    // print_state(&occupied, &shape);
-    for num in 0..rock_count {
-        // initial move
-        let direction = jets.next().unwrap();
+// What draw and the recording show in each cell
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Rock,
+    Falling,
+    Wall,
+    Floor,
+    Corner,
+}
+
+struct Chamber<'a> {
+    jets: &'a [Jet],
+    next_jet: usize,
+    occupied: SparseGrid<u64, ()>,
+    highest_row: u64,
+    shape: Rock,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a [Jet]) -> Chamber<'a> {
+        Chamber {
+            jets,
+            next_jet: 0,
+            occupied: SparseGrid::new(),
+            highest_row: 0,
+            // initial shape
+            shape: Rock {
+                shape: Box::new(HLine::new(get_starting_coordinate(0))),
+                shape_type: ShapeType::HLine,
+            },
+        }
+    }
+
+    fn push(&mut self) {
+        let direction = &self.jets[self.next_jet];
+        self.next_jet = (self.next_jet + 1) % self.jets.len();
         if let Some(next_shape) = match direction {
-            Jet::Left => shape.shape.move_left(&occupied),
-            Jet::Right => shape.shape.move_right(&occupied),
+            Jet::Left => self.shape.shape.move_left(&self.occupied),
+            Jet::Right => self.shape.shape.move_right(&self.occupied),
         } {
-            jet_usage_count += 1;
-            shape.shape = next_shape;
-        }
-
-        // Descend rock
-        while let Some(next_shape) = shape.shape.descend(&occupied) {
-            shape.shape = next_shape;
-            let direction = jets.next().unwrap();
-            if let Some(next_shape) = match direction {
-                Jet::Left => shape.shape.move_left(&occupied),
-                Jet::Right => shape.shape.move_right(&occupied),
-            } {
-                shape.shape = next_shape;
-            }
-            jet_usage_count += 1;
+            self.shape.shape = next_shape;
         }
+    }
 
+    // Blow the falling rock about until it lands, then start the next one
+    fn drop_rock(&mut self) {
+        self.push();
+        while let Some(next_shape) = self.shape.shape.descend(&self.occupied) {
+            self.shape.shape = next_shape;
+            self.push();
+        }
         // shape stopped where it landed
-        occupied.extend(shape.shape.get_coordinates());
-        if shape.shape.get_highest().row > highest_row {
-            highest_row = shape.shape.get_highest().row;
-        }
-        shape = get_next_shape(shape, get_starting_coordinate(highest_row));
-        let next_state = (
-            get_occupied_state(&occupied, highest_row),
-            jet_usage_count % jet_cycle,
-            shape.shape_type,
+        self.occupied.extend(self.shape.shape.get_coordinates());
+        self.highest_row = self.highest_row.max(self.shape.shape.get_highest().row);
+        self.shape = get_next_shape(
+            self.shape.shape_type,
+            get_starting_coordinate(self.highest_row),
         );
-        if states.contains_key(&next_state) {
-            return calculate_result(states, next_state, num, rock_count, highest_row);
-        }
-        states.insert(next_state, (highest_row, num));
     }
-    highest_row
+
+    // The chamber as drawn in the puzzle, from the floor up to the falling rock
+    #[allow(dead_code)]
+    fn draw(&self) -> String {
+        let top = self.shape.shape.get_highest().row;
+        self.window(top + 1).to_text(&self.legend())
+    }
+
+    // The top rows of the chamber, down to the floor if it's low enough
+    fn window(&self, rows: u64) -> Picture<Tile> {
+        let top = self.shape.shape.get_highest().row;
+        let bottom = (top + 1).saturating_sub(rows);
+        let walls = (bottom..=top)
+            .flat_map(|row| [0, 8].map(|col| (PosCoordinate { row, col }, Tile::Wall)));
+        let floor = (0..=8).map(|col| {
+            let tile = if col % 8 == 0 {
+                Tile::Corner
+            } else {
+                Tile::Floor
+            };
+            (PosCoordinate { row: 0, col }, tile)
+        });
+        let cells = self
+            .occupied
+            .coordinates()
+            .map(|c| (c, Tile::Rock))
+            .chain(
+                self.shape
+                    .shape
+                    .get_coordinates()
+                    .into_iter()
+                    .map(|c| (c, Tile::Falling)),
+            )
+            .chain(walls)
+            .chain(floor);
+        let window = Bounds {
+            min: PosCoordinate {
+                row: bottom,
+                col: 0,
+            },
+            max: PosCoordinate { row: top, col: 8 },
+        };
+        Picture::from_cells_within(cells, window, Origin::BottomLeft)
+    }
+
+    fn history(&self) -> History {
+        (
+            get_occupied_state(&self.occupied, self.highest_row),
+            self.next_jet,
+            self.shape.shape_type,
+        )
+    }
 }
 
-fn calculate_result(
-    states: HashMap<History, HistoryValue>,
-    next_state: History,
-    num: u64,
-    rock_count: u64,
-    highest_row: u64,
-) -> u64 {
-    // we found a loop
-    let previous: &(u64, u64) = states.get(&next_state).unwrap();
-    // also add in the number of rocks (rock_count % cycle length - init_window)
-    let cycle_length = num - previous.1;
-    let cycle_height = highest_row - previous.0;
-    let remainder = rock_count % cycle_length;
-    let gap = if remainder > previous.1 {
-        remainder - previous.1
-    } else {
-        remainder + cycle_length - previous.1
-    };
-    let repetions = if remainder > previous.1 {
-        rock_count.div_euclid(cycle_length)
-    } else {
-        rock_count.div_euclid(cycle_length) - 1
-    };
-    cycle_height * repetions
-        + (states
-            .values()
-            .find(|(_, n)| *n == previous.1 + gap - 1)
-            .unwrap()
-            .0)
+// How much of the chamber a recording shows, as it only ever grows upwards
+const RECORDED_ROWS: u64 = 40;
+
+// One rock at a time, forever
+impl Simulation for Chamber<'_> {
+    type Kind = Tile;
+
+    fn step(&mut self) -> bool {
+        self.drop_rock();
+        true
+    }
+
+    fn picture(&self) -> Picture<Tile> {
+        self.window(RECORDED_ROWS)
+    }
+
+    fn legend(&self) -> Legend<Tile> {
+        Legend::new(Style::new('.', Rgb::WHITE, "air"))
+            .with(Tile::Rock, Style::new('#', Rgb(96, 96, 96), "rock"))
+            .with(
+                Tile::Falling,
+                Style::new('@', Rgb(200, 60, 40), "falling rock"),
+            )
+            .with(Tile::Wall, Style::new('|', Rgb::BLACK, "wall"))
+            .with(Tile::Floor, Style::new('-', Rgb::BLACK, "floor"))
+            .with(Tile::Corner, Style::new('+', Rgb::BLACK, "corner"))
+    }
+}
+
+fn solve(input: &Input, rock_count: u64) -> u64 {
+    cycle::find_cycle(
+        Chamber::new(&input.jets),
+        Chamber::drop_rock,
+        Chamber::history,
+        |chamber| chamber.highest_row,
+    )
+    .extrapolate(rock_count)
 }
 
-fn get_occupied_state(occupied: &HashSet<PosCoordinate>, highest_row: u64) -> (u8, u8, u8) {
+fn get_occupied_state(occupied: &SparseGrid<u64, ()>, highest_row: u64) -> (u8, u8, u8) {
     (
         row_to_int(occupied, highest_row),
         if highest_row > 1 {
@@ -829,48 +906,15 @@ fn get_occupied_state(occupied: &HashSet<PosCoordinate>, highest_row: u64) -> (u
     )
 }
 
-fn row_to_int(occupied: &HashSet<PosCoordinate>, row: u64) -> u8 {
+fn row_to_int(occupied: &SparseGrid<u64, ()>, row: u64) -> u8 {
     (1..=7)
         .map(|col| PosCoordinate { col, row })
-        .map(|c| occupied.contains(&c) as u8)
+        .map(|c| occupied.contains(c) as u8)
         .enumerate()
         .fold(0, |num, (i, value)| num | (value << i))
 }
 
-#[allow(dead_code)]
-fn print_state(occupied: &HashSet<PosCoordinate>, shape: &Rock) {
-    // highest point a rock can be
-    let start = shape.shape.get_highest().row;
-    let rock_coords: HashSet<PosCoordinate> = shape.shape.get_coordinates().into_iter().collect();
-    for row in (0..=start).rev() {
-        for col in 0..=8 {
-            if row == 0 {
-                if col == 0 || col == 8 {
-                    print!("+");
-                    continue;
-                } else {
-                    print!("-");
-                    continue;
-                }
-            } else if col == 0 || col == 8 {
-                print!("|");
-                continue;
-            }
-            let c = PosCoordinate { row, col };
-            if occupied.contains(&c) {
-                print!("#");
-            } else if rock_coords.contains(&c) {
-                print!("@");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-    println!();
-}
-
-fn solve_part2(input: &Input) -> u64 {
+pub fn solve_part2(input: &Input) -> u64 {
     // aaabbbcccdddeee
     // <<><><<><><<><>
     // need fall time * jets.len
@@ -883,7 +927,7 @@ mod tests {
 
     #[test]
     fn test_part1() -> color_eyre::Result<()> {
-        let input = parsing::parse_input(include_str!("../../input/day17.test.txt"));
+        let input = parsing::parse_input(include_str!("../../input/day17.test.txt"))?;
         let result = solve_part1(&input);
         assert_eq!(result, 3068);
         Ok(())
@@ -891,9 +935,33 @@ mod tests {
 
     #[test]
     fn test_part2() -> color_eyre::Result<()> {
-        let input = parsing::parse_input(include_str!("../../input/day17.test.txt"));
+        let input = parsing::parse_input(include_str!("../../input/day17.test.txt"))?;
         let result = solve_part2(&input);
         assert_eq!(result, 1514285714288);
         Ok(())
     }
+
+    #[test]
+    fn test_draw() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day17.test.txt"))?;
+        let mut chamber = Chamber::new(&input.jets);
+        chamber.drop_rock();
+        chamber.drop_rock();
+        // as shown in the puzzle when the third rock appears
+        let expected = "\
+|....@..|
+|....@..|
+|..@@@..|
+|.......|
+|.......|
+|.......|
+|...#...|
+|..###..|
+|...#...|
+|..####.|
++-------+
+";
+        assert_eq!(chamber.draw(), expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use crate::{search, Bounds3, Coordinate3, Solution};
+
+pub type Cube = Coordinate3<i32>;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input<'a> = Vec<Cube>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub mod parsing {
+    use super::Cube;
+    use crate::parsers::{lines, run, xyz};
+
+    pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Cube>> {
+        run(lines(xyz), input)
+    }
+}
+
+pub fn solve_part1(input: &[Cube]) -> usize {
+    let cubes: HashSet<Cube> = input.iter().copied().collect();
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbours())
+        .filter(|adj| !cubes.contains(adj))
+        .count()
+}
+
+pub fn solve_part2(input: &[Cube]) -> usize {
+    let cubes: HashSet<Cube> = input.iter().copied().collect();
+    // leave a layer of air all round so the fill can reach every side, and
+    // start from a corner of it which can't be part of the droplet
+    let bounds = match Bounds3::from_coordinates(cubes.iter().copied()) {
+        Some(bounds) => bounds.grow(1),
+        None => return 0,
+    };
+    let outside = search::flood_fill([bounds.min], |cube| {
+        cube.neighbours()
+            .into_iter()
+            .filter(|&adj| bounds.contains(adj) && !cubes.contains(&adj))
+    });
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbours())
+        .filter(|adj| outside.contains(adj))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day18.test.txt"))?;
+        let result = solve_part1(&input);
+        assert_eq!(result, 64);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day18.test.txt"))?;
+        let result = solve_part2(&input);
+        assert_eq!(result, 58);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_touching_origin() -> color_eyre::Result<()> {
+        // a hollow 3x3x3 shell with a corner at the origin, the air inside
+        // doesn't count
+        let shell = Bounds3::from_coordinates([Cube::new(0, 0, 0), Cube::new(2, 2, 2)])
+            .unwrap()
+            .coordinates()
+            .filter(|&c| c != Cube::new(1, 1, 1))
+            .collect::<Vec<_>>();
+        assert_eq!(solve_part1(&shell), 54 + 6);
+        assert_eq!(solve_part2(&shell), 54);
+        Ok(())
+    }
+}
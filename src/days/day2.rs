@@ -1,13 +1,12 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
 use reformation::Reformation;
 
+use crate::{parsers::each_line, Solution};
+
 #[derive(Reformation, Eq, PartialEq, Debug, Clone, Copy)]
 #[reformation(r"{opponent} {me}")]
-struct Input {
+pub struct Input {
     opponent: Choice,
-    me: DesiredResult,
+    me: MeInput,
 }
 
 #[derive(Reformation, Eq, PartialEq, Debug, Clone, Copy)]
@@ -20,7 +19,7 @@ enum Choice {
     Scissors,
 }
 
-#[derive(Reformation, Eq, PartialEq, Debug)]
+#[derive(Reformation, Eq, PartialEq, Debug, Clone, Copy)]
 enum MeInput {
     #[reformation(r"X")]
     Rock,
@@ -30,56 +29,83 @@ enum MeInput {
     Scissors,
 }
 
-#[derive(Reformation, Eq, PartialEq, Debug, Clone, Copy)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
 enum DesiredResult {
-    #[reformation(r"X")]
     Lose,
-    #[reformation(r"Y")]
     Draw,
-    #[reformation(r"Z")]
     Win,
 }
 
+impl From<MeInput> for DesiredResult {
+    fn from(me: MeInput) -> Self {
+        match me {
+            MeInput::Rock => DesiredResult::Lose,
+            MeInput::Paper => DesiredResult::Draw,
+            MeInput::Scissors => DesiredResult::Win,
+        }
+    }
+}
+
 enum GameResult {
     Win,
     Lose,
     Draw,
 }
 
-fn main() {
-    let f = File::open("input/day2.txt").unwrap();
-    let reader = BufReader::new(f);
-    let result: i32 = reader
-        .lines()
-        .map(|line| Input::parse(&line.unwrap()).unwrap())
-        .map(get_score)
-        .sum();
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input<'a> = Vec<Input>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
+    each_line(input, "two letters like \"A Y\"", Input::parse)
+}
 
-    println!("Part: {:?}", result);
+pub fn solve_part1(input: &[Input]) -> i32 {
+    input.iter().copied().map(get_score_part1).sum()
 }
 
-// fn get_score(input: Input) -> i32 {
-//     let choice = match input.me {
-//         MeInput::Rock => 1,
-//         MeInput::Paper => 2,
-//         MeInput::Scissors => 3,
-//     };
-//     let result = match who_wins(input) {
-//         GameResult::Win => 6,
-//         GameResult::Lose => 0,
-//         GameResult::Draw => 3,
-//     };
-//     choice + result
-// }
+pub fn solve_part2(input: &[Input]) -> i32 {
+    input.iter().copied().map(get_score).sum()
+}
+
+fn get_score_part1(input: Input) -> i32 {
+    let choice = match input.me {
+        MeInput::Rock => Choice::Rock,
+        MeInput::Paper => Choice::Paper,
+        MeInput::Scissors => Choice::Scissors,
+    };
+    score_round(input.opponent, choice)
+}
 
 fn get_score(input: Input) -> i32 {
-    let choice = get_choice(input);
+    score_round(input.opponent, get_choice(input))
+}
+
+fn score_round(opponent: Choice, choice: Choice) -> i32 {
     let choice_score = match choice {
         Choice::Rock => 1,
         Choice::Paper => 2,
         Choice::Scissors => 3,
     };
-    let result = match who_wins(input.opponent, choice) {
+    let result = match who_wins(opponent, choice) {
         GameResult::Win => 6,
         GameResult::Lose => 0,
         GameResult::Draw => 3,
@@ -88,18 +114,19 @@ fn get_score(input: Input) -> i32 {
 }
 
 fn get_choice(input: Input) -> Choice {
+    let desired = DesiredResult::from(input.me);
     match input.opponent {
-        Choice::Rock => match input.me {
+        Choice::Rock => match desired {
             DesiredResult::Lose => Choice::Scissors,
             DesiredResult::Draw => Choice::Rock,
             DesiredResult::Win => Choice::Paper,
         },
-        Choice::Paper => match input.me {
+        Choice::Paper => match desired {
             DesiredResult::Lose => Choice::Rock,
             DesiredResult::Draw => Choice::Paper,
             DesiredResult::Win => Choice::Scissors,
         },
-        Choice::Scissors => match input.me {
+        Choice::Scissors => match desired {
             DesiredResult::Lose => Choice::Paper,
             DesiredResult::Draw => Choice::Scissors,
             DesiredResult::Win => Choice::Rock,
@@ -1,49 +1,43 @@
 use std::{
     cmp::Ordering::{Equal, Greater, Less},
     collections::HashMap,
-    time::Instant,
 };
 
 use itertools::Itertools;
 
-fn main() -> color_eyre::Result<()> {
-    let input = parsing::parse_input(include_str!("../../input/day20.txt"))?;
-    let time = Instant::now();
-    println!(
-        "Part 1: {} in {}ms",
-        solve_part1(&input),
-        time.elapsed().as_millis()
-    );
-    println!(
-        "Part 1 alt: {} in {}ms",
-        solve_part1_alt(&input),
-        time.elapsed().as_millis()
-    );
-    let time = Instant::now();
-    println!(
-        "Part 2: {} in {}ms",
-        solve_part2(&input),
-        time.elapsed().as_millis()
-    );
-    Ok(())
-}
+use crate::Solution;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input<'a> = Vec<i32>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
 
-mod parsing {
-    use nom::{Finish, IResult};
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part1(input))
+    }
 
-    fn parse_line(input: &str) -> IResult<&str, i32> {
-        (nom::character::complete::i32)(input)
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part2(input))
     }
+}
+
+pub mod parsing {
+    use crate::parsers::{integer, lines, run};
 
     pub fn parse_input(input: &str) -> color_eyre::Result<Vec<i32>> {
-        Ok(input
-            .lines()
-            .map(|line| parse_line(line).finish().unwrap().1)
-            .collect())
+        run(lines(integer), input)
     }
 }
 
-fn solve_part1(input: &[i32]) -> i32 {
+pub fn solve_part1(input: &[i32]) -> i32 {
     let size = input.len();
     let mut output: HashMap<(i32, usize), usize> = build_map(input);
     // move numbers in the order they appear in the input
@@ -71,6 +65,7 @@ fn solve_part1(input: &[i32]) -> i32 {
         .sum()
 }
 
+#[allow(dead_code)]
 fn solve_part1_alt(input: &[i32]) -> i32 {
     let size = input.len();
     let mut output: Vec<(usize, i32)> = input.iter().copied().enumerate().collect_vec();
@@ -90,6 +85,7 @@ fn solve_part1_alt(input: &[i32]) -> i32 {
         + output[(start_index + 3000) % size].1
 }
 
+#[allow(dead_code)]
 fn move_element(output: &mut Vec<(usize, i32)>, target: (usize, i32), size: i32) {
     let start = output.iter().find_position(|&n| *n == target).unwrap().0;
     let end = ((start as i32) + target.1).rem_euclid(size);
@@ -143,7 +139,7 @@ fn move_element_hash(output: &mut HashMap<(i32, usize), usize>, target: (i32, us
     })
 }
 
-fn solve_part2(_input: &[i32]) -> i32 {
+pub fn solve_part2(_input: &[i32]) -> i32 {
     1
 }
 
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt::Display, time::Instant};
+use std::{collections::HashMap, fmt::Display};
+
+use color_eyre::eyre::ContextCompat;
+
+use crate::Solution;
 
 #[derive(Debug, Clone)]
 pub struct Input<'a> {
@@ -59,60 +63,63 @@ impl Display for Operator {
     }
 }
 
-fn main() -> color_eyre::Result<()> {
-    let input = parsing::parse_input(include_str!("../../input/day21.txt"))?;
-    let time = Instant::now();
-    println!(
-        "Part 1: {} in {}ms",
-        solve_part1(&input),
-        time.elapsed().as_millis()
-    );
-    let time = Instant::now();
-    println!(
-        "Part 2: {} in {}ms",
-        solve_part2(&input).unwrap(),
-        time.elapsed().as_millis()
-    );
-    Ok(())
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input<'a> = Vec<Input<'a>>;
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i64> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i64> {
+        solve_part2(input).wrap_err("No value to shout found")
+    }
 }
 
-mod parsing {
-    use crate::{Operation, Operator, Value};
+pub mod parsing {
+    use super::{Operation, Operator, Value};
 
     use super::Input;
     use nom::{
         branch::alt,
-        bytes::complete::{tag, take, take_till},
+        bytes::complete::tag,
+        character::complete::{alpha1, one_of},
         combinator::map,
+        error::context,
         sequence::{delimited, separated_pair, tuple},
-        Finish, IResult,
     };
 
-    fn parse_name(input: &str) -> IResult<&str, &str> {
-        take_till(|c| c == ':')(input)
-    }
+    use crate::parsers::{integer, lines, run, IResult};
 
-    fn take1(input: &str) -> IResult<&str, &str> {
-        take(1usize)(input)
+    fn parse_name(input: &str) -> IResult<&str, &str> {
+        context("a monkey's name", alpha1)(input)
     }
 
     fn parse_operator(input: &str) -> IResult<&str, Operator> {
         delimited(
             tag(" "),
-            map(take1, |c| match c {
-                "+" => Operator::Add,
-                "*" => Operator::Multiply,
-                "-" => Operator::Subtract,
-                "/" => Operator::Divide,
-                _ => panic!("Invalid character"),
+            map(context("+, -, * or /", one_of("+*-/")), |c| match c {
+                '+' => Operator::Add,
+                '*' => Operator::Multiply,
+                '-' => Operator::Subtract,
+                _ => Operator::Divide,
             }),
             tag(" "),
         )(input)
     }
 
-    fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    fn parse_operation(input: &str) -> IResult<&str, Operation<'_>> {
         map(
-            tuple((take(4usize), parse_operator, take(4usize))),
+            tuple((parse_name, parse_operator, parse_name)),
             |(left, operator, right)| Operation {
                 left: Box::new(Value::Name(left)),
                 operator,
@@ -121,29 +128,29 @@ mod parsing {
         )(input)
     }
 
-    fn parse_value(input: &str) -> IResult<&str, Value> {
-        alt((
-            map(nom::character::complete::i64, Value::Constant),
-            map(parse_operation, Value::Equation),
-        ))(input)
+    fn parse_value(input: &str) -> IResult<&str, Value<'_>> {
+        context(
+            "a number or an operation",
+            alt((
+                map(integer, Value::Constant),
+                map(parse_operation, Value::Equation),
+            )),
+        )(input)
     }
 
-    fn parse_line(input: &str) -> IResult<&str, Input> {
+    fn parse_line(input: &str) -> IResult<&str, Input<'_>> {
         map(
-            separated_pair(parse_name, tag(": "), parse_value),
+            separated_pair(parse_name, context("\": \"", tag(": ")), parse_value),
             |(name, value)| Input { name, value },
         )(input)
     }
 
-    pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
-        Ok(input
-            .lines()
-            .map(|line| parse_line(line).finish().unwrap().1)
-            .collect())
+    pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input<'_>>> {
+        run(lines(parse_line), input)
     }
 }
 
-fn solve_part1(input: &[Input]) -> i64 {
+pub fn solve_part1(input: &[Input]) -> i64 {
     let monkey_values: HashMap<&str, Value> = input
         .iter()
         .map(|monkey| (monkey.name, monkey.value.clone()))
@@ -195,13 +202,13 @@ fn find_value_for_monkey<'a>(
     }
 }
 
-fn solve_part2(input: &[Input]) -> Option<i64> {
+pub fn solve_part2(input: &[Input]) -> Option<i64> {
     let monkey_values: HashMap<&str, Value> = input
         .iter()
         .map(|monkey| (monkey.name, monkey.value.clone()))
         .collect();
     let end_value = find_value_for_monkey("root", &monkey_values, 2);
-    println!("{}", end_value.unwrap());
+    log::debug!("root works out as {:?}", end_value);
     Some(1)
 }
 
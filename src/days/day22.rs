@@ -0,0 +1,915 @@
+use std::collections::HashMap;
+
+use crate::{
+    parse_param,
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Direction, IndexingCoordinate, Part, Solution,
+};
+use color_eyre::eyre::{bail, ensure};
+use itertools::Itertools;
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    map: Map,
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    width: usize,
+    height: usize,
+    terrain: Vec<Contents>,
+}
+
+impl Map {
+    fn get_contents(&self, coordinate: IndexingCoordinate) -> Option<Contents> {
+        if coordinate.row < 1
+            || coordinate.col < 1
+            || coordinate.row > self.height
+            || coordinate.col > self.width
+        {
+            None
+        } else {
+            Some(self.terrain[(coordinate.row - 1) * self.width + (coordinate.col - 1)])
+        }
+    }
+
+    // The map with the path walked so far drawn over it, facing the way it
+    // last went through each tile
+    fn draw(&self, visited: &HashMap<IndexingCoordinate, Direction>) -> String {
+        self.picture(visited).to_text(&Map::legend())
+    }
+
+    fn picture(&self, visited: &HashMap<IndexingCoordinate, Direction>) -> Picture<Tile> {
+        let mut picture = Picture::new(self.width, self.height);
+        for row in 1..=self.height {
+            for col in 1..=self.width {
+                let pos = IndexingCoordinate { row, col };
+                let tile = match (visited.get(&pos), self.get_contents(pos)) {
+                    (Some(&facing), _) => Tile::Path(facing),
+                    (None, Some(Contents::Rock)) => Tile::Wall,
+                    (None, Some(Contents::Empty)) => Tile::Open,
+                    (None, _) => continue,
+                };
+                picture.set(row - 1, col - 1, tile);
+            }
+        }
+        picture
+    }
+
+    fn legend() -> Legend<Tile> {
+        Direction::ALL.iter().fold(
+            Legend::new(Style::new(' ', Rgb::WHITE, "off the map"))
+                .with(Tile::Open, Style::new('.', Rgb(220, 220, 220), "open"))
+                .with(Tile::Wall, Style::new('#', Rgb::BLACK, "wall")),
+            |legend, &facing| {
+                legend.with(
+                    Tile::Path(facing),
+                    Style::new(facing.arrow(), Rgb(200, 60, 40), "path"),
+                )
+            },
+        )
+    }
+
+    // find the column of the first non void element in the row
+    fn first_in_row(&self, row: usize) -> usize {
+        // fucking 1-indexing
+        (1..=self.width)
+            .find(|col| {
+                !matches!(
+                    self.get_contents(IndexingCoordinate { row, col: *col })
+                        .unwrap(),
+                    Contents::Void
+                )
+            })
+            .unwrap()
+    }
+
+    fn last_in_row(&self, row: usize) -> usize {
+        (1..=self.width)
+            .rev()
+            .find(|col| {
+                !matches!(
+                    self.get_contents(IndexingCoordinate { row, col: *col })
+                        .unwrap(),
+                    Contents::Void
+                )
+            })
+            .unwrap()
+    }
+
+    fn first_in_col(&self, col: usize) -> usize {
+        (1..=self.height)
+            .find(|row| {
+                !matches!(
+                    self.get_contents(IndexingCoordinate { row: *row, col })
+                        .unwrap(),
+                    Contents::Void
+                )
+            })
+            .unwrap()
+    }
+
+    fn last_in_col(&self, col: usize) -> usize {
+        (1..=self.height)
+            .rev()
+            .find(|row| {
+                !matches!(
+                    self.get_contents(IndexingCoordinate { row: *row, col })
+                        .unwrap(),
+                    Contents::Void
+                )
+            })
+            .unwrap()
+    }
+
+    fn wrap_from_position(
+        &self,
+        position: IndexingCoordinate,
+        facing: Direction,
+    ) -> IndexingCoordinate {
+        match facing {
+            Direction::East => IndexingCoordinate {
+                col: self.first_in_row(position.row),
+                ..position
+            },
+            Direction::South => IndexingCoordinate {
+                row: self.first_in_col(position.col),
+                ..position
+            },
+            Direction::West => IndexingCoordinate {
+                col: self.last_in_row(position.row),
+                ..position
+            },
+            Direction::North => IndexingCoordinate {
+                row: self.last_in_col(position.col),
+                ..position
+            },
+        }
+    }
+
+    // Return the (target block, target coordinate on that block, new facing)
+    fn wrap_from_position_connected(
+        &self,
+        position: IndexingCoordinate,
+        facing: Direction,
+        connections: &Connections,
+    ) -> (IndexingCoordinate, IndexingCoordinate, Direction) {
+        let (block, target_edge) = match facing {
+            Direction::East => connections.right,
+            Direction::South => connections.bottom,
+            Direction::West => connections.left,
+            Direction::North => connections.top,
+        };
+        let target_facing = match target_edge {
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::North => Direction::South,
+        };
+        let target_position = match (facing, target_edge) {
+            (Direction::East, Direction::East) => IndexingCoordinate {
+                row: self.height - position.row + 1,
+                col: position.col,
+            },
+            (Direction::East, Direction::South) => position.transpose(),
+            (Direction::East, Direction::West) => IndexingCoordinate {
+                row: position.row,
+                col: self.width - position.col + 1,
+            },
+            (Direction::East, Direction::North) => IndexingCoordinate {
+                // should be 1
+                row: self.height - position.col + 1,
+                col: self.width - position.row + 1,
+            },
+            (Direction::South, Direction::East) => position.transpose(),
+            (Direction::South, Direction::South) => IndexingCoordinate {
+                row: position.row,
+                col: self.width - position.col + 1,
+            },
+            (Direction::South, Direction::West) => IndexingCoordinate {
+                row: self.height - position.col + 1,
+                col: self.width - position.row + 1,
+            },
+            (Direction::South, Direction::North) => IndexingCoordinate {
+                row: self.height - position.row + 1,
+                col: position.col,
+            },
+            (Direction::West, Direction::East) => IndexingCoordinate {
+                row: position.row,
+                col: self.width - position.col + 1,
+            },
+            (Direction::West, Direction::South) => IndexingCoordinate {
+                row: self.height - position.col + 1,
+                col: self.width - position.row + 1,
+            },
+            (Direction::West, Direction::West) => IndexingCoordinate {
+                row: self.height - position.row + 1,
+                col: position.col,
+            },
+            (Direction::West, Direction::North) => position.transpose(),
+            (Direction::North, Direction::East) => IndexingCoordinate {
+                row: self.height - position.col + 1,
+                col: self.width - position.row + 1,
+            },
+            (Direction::North, Direction::South) => IndexingCoordinate {
+                row: self.height - position.row + 1,
+                col: position.col,
+            },
+            (Direction::North, Direction::West) => position.transpose(),
+            (Direction::North, Direction::North) => IndexingCoordinate {
+                row: position.row,
+                col: self.width - position.col + 1,
+            },
+        };
+        (block, target_position, target_facing)
+    }
+}
+
+// What draw and the recording show in each cell, anything off the map is left empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Open,
+    Wall,
+    Path(Direction),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Contents {
+    Rock,
+    Empty,
+    Void,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Move(u32),
+    Rotate(Rotation),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Rotation {
+    Clockwise,
+    CounterClockwise,
+}
+
+// side length of each face of the cube in part 2
+pub struct Params {
+    pub grid_size: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { grid_size: 50 }
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Input<'a> = Input;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn example_params() -> Params {
+        Params { grid_size: 4 }
+    }
+
+    fn set_param(params: &mut Params, name: &str, value: &str) -> color_eyre::Result<()> {
+        match name {
+            "grid_size" => match parse_param(name, value)? {
+                0 => bail!("grid_size must be at least 1"),
+                size => params.grid_size = size,
+            },
+            _ => bail!("Day 22 has no parameter {}", name),
+        }
+        Ok(())
+    }
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &Self::Params) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, params: &Self::Params) -> color_eyre::Result<usize> {
+        solve_part2(input, params.grid_size)
+    }
+
+    fn record(
+        input: &Self::Input<'_>,
+        params: &Params,
+        part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        Ok(Some(match part {
+            Part::One => recorder.record(&mut Walker::new(input)),
+            Part::Two => recorder.record(&mut CubeWalker::new(input, params.grid_size)?),
+        }))
+    }
+}
+
+pub mod parsing {
+    use std::iter::repeat_n;
+
+    use super::{Contents, Instruction, Map, Rotation};
+
+    use super::Input;
+    use crate::parsers::{rows, run, IResult};
+    use itertools::Itertools;
+    use nom::{
+        branch::alt, bytes::complete::tag, character::complete::one_of, combinator::map,
+        error::context, multi::many1, sequence::separated_pair,
+    };
+
+    fn parse_char(input: &str) -> IResult<&str, Contents> {
+        map(one_of(" .#"), |c| match c {
+            ' ' => Contents::Void,
+            '.' => Contents::Empty,
+            _ => Contents::Rock,
+        })(input)
+    }
+
+    fn parse_map(input: &str) -> IResult<&str, Map> {
+        map(rows(parse_char), |list_of_lists| {
+            let max_length = list_of_lists.iter().map(|l| l.len()).max().unwrap();
+            Map {
+                width: max_length,
+                height: list_of_lists.len(),
+                terrain: list_of_lists
+                    .into_iter()
+                    .flat_map(|mut l| {
+                        if l.len() < max_length {
+                            // extend the array to the length of the longest row
+                            l.extend(repeat_n(Contents::Void, max_length - l.len()));
+                        }
+                        assert_eq!(l.len(), max_length);
+                        l
+                    })
+                    .collect_vec(),
+            }
+        })(input)
+    }
+
+    fn parse_rotation(input: &str) -> IResult<&str, Instruction> {
+        map(alt((tag("R"), tag("L"))), |c| match c {
+            "R" => Instruction::Rotate(Rotation::Clockwise),
+            "L" => Instruction::Rotate(Rotation::CounterClockwise),
+            _ => unreachable!("We only match those two"),
+        })(input)
+    }
+
+    fn parse_move(input: &str) -> IResult<&str, Instruction> {
+        map(nom::character::complete::u32, Instruction::Move)(input)
+    }
+
+    fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+        many1(context(
+            "a turn (L or R) or a number of tiles",
+            alt((parse_rotation, parse_move)),
+        ))(input)
+    }
+
+    fn parse_data(input: &str) -> IResult<&str, Input> {
+        map(
+            separated_pair(
+                parse_map,
+                context("a blank line after the map", tag("\n\n")),
+                parse_instructions,
+            ),
+            |(map, instructions)| Input { map, instructions },
+        )(input)
+    }
+
+    pub fn parse_input(input: &str) -> color_eyre::Result<Input> {
+        run(parse_data, input)
+    }
+}
+
+// Rows and columns start at 1, so there's always room to step off the map
+fn move_direction(coordinate: IndexingCoordinate, facing: Direction) -> IndexingCoordinate {
+    coordinate.checked_offset(facing.delta()).unwrap()
+}
+
+// Following the instructions round the flat map one at a time, see Simulation
+struct Walker<'a> {
+    map: &'a Map,
+    instructions: &'a [Instruction],
+    next: usize,
+    position: IndexingCoordinate,
+    facing: Direction,
+    visited: HashMap<IndexingCoordinate, Direction>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(input: &'a Input) -> Walker<'a> {
+        let position = IndexingCoordinate {
+            row: 1,
+            col: input.map.first_in_row(1),
+        };
+        Walker {
+            map: &input.map,
+            instructions: &input.instructions,
+            next: 0,
+            position,
+            facing: Direction::East,
+            visited: HashMap::new(),
+        }
+    }
+
+    fn walk(&mut self, distance: u32) {
+        let map = self.map;
+        for _ in 0..distance {
+            let next_coord = move_direction(self.position, self.facing);
+            match map.get_contents(next_coord) {
+                None | Some(Contents::Void) => {
+                    let alt_coord = map.wrap_from_position(self.position, self.facing);
+                    match map.get_contents(alt_coord) {
+                        Some(Contents::Rock) => {
+                            break;
+                        }
+                        Some(Contents::Empty) => {
+                            self.position = alt_coord;
+                        }
+                        _ => unreachable!("Invalid contents"),
+                    }
+                }
+                Some(Contents::Rock) => {
+                    // we stop
+                    break;
+                }
+                Some(Contents::Empty) => self.position = next_coord,
+            }
+            self.visited.insert(self.position, self.facing);
+        }
+    }
+}
+
+impl Simulation for Walker<'_> {
+    type Kind = Tile;
+
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.instructions.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+        match instruction {
+            Instruction::Move(distance) => self.walk(*distance),
+            Instruction::Rotate(Rotation::Clockwise) => self.facing = self.facing.turn_right(),
+            Instruction::Rotate(Rotation::CounterClockwise) => {
+                self.facing = self.facing.turn_left()
+            }
+        }
+        self.visited.insert(self.position, self.facing);
+        true
+    }
+
+    fn picture(&self) -> Picture<Tile> {
+        self.map.picture(&self.visited)
+    }
+
+    fn legend(&self) -> Legend<Tile> {
+        Map::legend()
+    }
+}
+
+pub fn solve_part1(input: &Input) -> usize {
+    let mut walker = Walker::new(input);
+    while walker.step() {}
+    log::debug!("Day 22 path:\n{}", input.map.draw(&walker.visited));
+    get_score(walker.position, walker.facing)
+}
+
+fn get_score(position: IndexingCoordinate, facing: Direction) -> usize {
+    position.row * 1000
+        + position.col * 4
+        + match facing {
+            Direction::East => 0,
+            Direction::South => 1,
+            Direction::West => 2,
+            Direction::North => 3,
+        }
+}
+
+// As Walker, but folding the map into a cube, see Simulation
+struct CubeWalker<'a> {
+    map: &'a Map,
+    instructions: &'a [Instruction],
+    next: usize,
+    grid_size: usize,
+    connections: HashMap<IndexingCoordinate, Connections>,
+    // each face of the cube as its own map
+    maps: HashMap<(usize, usize), Map>,
+    current_block: (usize, usize),
+    // relative to the current face. Need to translate back for the final score
+    position: IndexingCoordinate,
+    facing: Direction,
+    // on the flat map, to draw
+    visited: HashMap<IndexingCoordinate, Direction>,
+}
+
+impl<'a> CubeWalker<'a> {
+    fn new(input: &'a Input, grid_size: usize) -> color_eyre::Result<CubeWalker<'a>> {
+        let map = &input.map;
+        ensure!(
+            map.width.is_multiple_of(grid_size) && map.height.is_multiple_of(grid_size),
+            "The {}x{} map doesn't split into faces of size {}",
+            map.width,
+            map.height,
+            grid_size
+        );
+        // divide map into six cube faces
+        // get the top left corners of each grid
+        let blocks = (1..map.height)
+            .step_by(grid_size)
+            .cartesian_product((1..map.width).step_by(grid_size))
+            .map(|(row, col)| IndexingCoordinate { row, col })
+            .filter(|c| {
+                matches!(
+                    map.get_contents(*c),
+                    Some(Contents::Empty) | Some(Contents::Rock)
+                )
+            })
+            .map(|c| {
+                // normalise coordinates
+                ((c.row - 1) / grid_size, (c.col - 1) / grid_size)
+            })
+            .collect_vec();
+        ensure!(
+            blocks.len() == 6,
+            "Faces of size {} split the map into {} faces, not 6",
+            grid_size,
+            blocks.len()
+        );
+        // find adjacent edges
+        // we know the coordinates are in row, then col order. Pattern match them
+        // map from each block, to the blocks it's connected to
+        let connections = build_connections(&blocks)?;
+        let maps = blocks
+            .iter()
+            .map(|&b| (b, build_map(b, grid_size, map)))
+            .collect();
+        Ok(CubeWalker {
+            map,
+            instructions: &input.instructions,
+            next: 0,
+            grid_size,
+            connections,
+            maps,
+            current_block: blocks[0],
+            position: IndexingCoordinate { row: 1, col: 1 },
+            facing: Direction::East,
+            visited: HashMap::new(),
+        })
+    }
+
+    fn real_position(&self) -> IndexingCoordinate {
+        get_real_position(self.current_block, self.grid_size, self.position)
+    }
+
+    fn walk(&mut self, distance: u32) {
+        for _ in 0..distance {
+            let current_map = &self.maps[&self.current_block];
+            let next_coord = move_direction(self.position, self.facing);
+            match current_map.get_contents(next_coord) {
+                None => {
+                    let (new_block, alt_coord, new_facing) = current_map
+                        .wrap_from_position_connected(
+                            self.position,
+                            self.facing,
+                            &self.connections[&self.current_block.into()],
+                        );
+                    let new_block = (new_block.row, new_block.col);
+                    match self.maps[&new_block].get_contents(alt_coord) {
+                        Some(Contents::Rock) => {
+                            break;
+                        }
+                        Some(Contents::Empty) => {
+                            self.position = alt_coord;
+                            self.current_block = new_block;
+                            self.facing = new_facing;
+                        }
+                        _ => unreachable!("Invalid contents at {:?}", alt_coord),
+                    }
+                }
+                Some(Contents::Rock) => {
+                    // we stop
+                    break;
+                }
+                Some(Contents::Empty) => self.position = next_coord,
+                Some(Contents::Void) => unreachable!("There is no void now"),
+            }
+            self.visited.insert(self.real_position(), self.facing);
+        }
+    }
+}
+
+impl Simulation for CubeWalker<'_> {
+    type Kind = Tile;
+
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.instructions.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+        match instruction {
+            Instruction::Move(distance) => self.walk(*distance),
+            Instruction::Rotate(Rotation::Clockwise) => self.facing = self.facing.turn_right(),
+            Instruction::Rotate(Rotation::CounterClockwise) => {
+                self.facing = self.facing.turn_left()
+            }
+        }
+        self.visited.insert(self.real_position(), self.facing);
+        true
+    }
+
+    fn picture(&self) -> Picture<Tile> {
+        self.map.picture(&self.visited)
+    }
+
+    fn legend(&self) -> Legend<Tile> {
+        Map::legend()
+    }
+}
+
+pub fn solve_part2(input: &Input, grid_size: usize) -> color_eyre::Result<usize> {
+    let mut walker = CubeWalker::new(input, grid_size)?;
+    while walker.step() {}
+    log::debug!("Day 22 path:\n{}", input.map.draw(&walker.visited));
+    let real_position = walker.real_position();
+    log::debug!(
+        "Day 22 finished at {:?} in block {:?}",
+        real_position,
+        walker.current_block
+    );
+    Ok(get_score(real_position, walker.facing))
+}
+
+fn get_real_position(
+    block: (usize, usize),
+    grid_size: usize,
+    position: IndexingCoordinate,
+) -> IndexingCoordinate {
+    IndexingCoordinate {
+        row: block.0 * grid_size + position.row,
+        col: block.1 * grid_size + position.col,
+    }
+}
+
+fn build_map(block: (usize, usize), grid_size: usize, original_map: &Map) -> Map {
+    let terrain = original_map
+        .terrain
+        .iter()
+        .chunks(grid_size)
+        .into_iter()
+        // find the start of our block
+        .skip(block.0 * original_map.width + block.1)
+        // split into map width number of blocks
+        .chunks(original_map.width / grid_size)
+        .into_iter()
+        // grab the first one (which will be the block column we want)
+        .map(|mut chunk| chunk.next().unwrap())
+        .take(grid_size)
+        .flat_map(|chunk| chunk.collect_vec())
+        .copied()
+        // pull that bad boy out
+        .collect_vec();
+    Map {
+        width: grid_size,
+        height: grid_size,
+        terrain,
+    }
+}
+
+fn build_connections(
+    blocks: &[(usize, usize)],
+) -> color_eyre::Result<HashMap<IndexingCoordinate, Connections>> {
+    let connections = match blocks[..] {
+        [(0, 2), (1, 0), (1, 1), (1, 2), (2, 2), (2, 3)] => {
+            let mut conns = HashMap::new();
+            conns.insert(
+                (0, 2).into(),
+                Connections {
+                    top: ((1, 0).into(), Direction::North),
+                    left: ((1, 1).into(), Direction::North),
+                    right: ((2, 3).into(), Direction::East),
+                    bottom: ((1, 2).into(), Direction::North),
+                },
+            );
+            conns.insert(
+                (1, 0).into(),
+                Connections {
+                    top: ((0, 2).into(), Direction::North),
+                    left: ((2, 3).into(), Direction::South),
+                    right: ((1, 1).into(), Direction::West),
+                    bottom: ((2, 2).into(), Direction::South),
+                },
+            );
+            conns.insert(
+                (1, 1).into(),
+                Connections {
+                    top: ((0, 2).into(), Direction::West),
+                    left: ((1, 0).into(), Direction::East),
+                    right: ((1, 2).into(), Direction::West),
+                    bottom: ((2, 2).into(), Direction::West),
+                },
+            );
+            conns.insert(
+                (1, 2).into(),
+                Connections {
+                    top: ((0, 2).into(), Direction::South),
+                    left: ((1, 1).into(), Direction::East),
+                    right: ((2, 3).into(), Direction::North),
+                    bottom: ((2, 2).into(), Direction::North),
+                },
+            );
+            conns.insert(
+                (2, 2).into(),
+                Connections {
+                    top: ((1, 2).into(), Direction::South),
+                    left: ((1, 1).into(), Direction::South),
+                    right: ((2, 3).into(), Direction::West),
+                    bottom: ((1, 0).into(), Direction::South),
+                },
+            );
+            conns.insert(
+                (2, 3).into(),
+                Connections {
+                    top: ((1, 2).into(), Direction::East),
+                    left: ((2, 2).into(), Direction::East),
+                    right: ((0, 2).into(), Direction::East),
+                    bottom: ((1, 0).into(), Direction::West),
+                },
+            );
+            conns
+        }
+        [(0, 1), (0, 2), (1, 1), (2, 0), (2, 1), (3, 0)] => {
+            let mut conns = HashMap::new();
+            conns.insert(
+                (0, 1).into(),
+                Connections {
+                    top: ((3, 0).into(), Direction::West),
+                    left: ((2, 0).into(), Direction::West),
+                    right: ((0, 2).into(), Direction::West),
+                    bottom: ((1, 1).into(), Direction::North),
+                },
+            );
+            conns.insert(
+                (0, 2).into(),
+                Connections {
+                    top: ((3, 0).into(), Direction::South),
+                    left: ((0, 1).into(), Direction::East),
+                    right: ((2, 1).into(), Direction::East),
+                    bottom: ((1, 1).into(), Direction::East),
+                },
+            );
+            conns.insert(
+                (1, 1).into(),
+                Connections {
+                    top: ((0, 1).into(), Direction::South),
+                    left: ((2, 0).into(), Direction::North),
+                    right: ((0, 2).into(), Direction::South),
+                    bottom: ((2, 1).into(), Direction::North),
+                },
+            );
+            conns.insert(
+                (2, 0).into(),
+                Connections {
+                    top: ((1, 1).into(), Direction::West),
+                    left: ((0, 1).into(), Direction::West),
+                    right: ((2, 1).into(), Direction::West),
+                    bottom: ((3, 0).into(), Direction::North),
+                },
+            );
+            conns.insert(
+                (2, 1).into(),
+                Connections {
+                    top: ((1, 1).into(), Direction::South),
+                    left: ((2, 0).into(), Direction::East),
+                    right: ((0, 2).into(), Direction::East),
+                    bottom: ((3, 0).into(), Direction::East),
+                },
+            );
+            conns.insert(
+                (3, 0).into(),
+                Connections {
+                    top: ((2, 0).into(), Direction::South),
+                    left: ((0, 1).into(), Direction::North),
+                    right: ((2, 1).into(), Direction::South),
+                    bottom: ((0, 2).into(), Direction::North),
+                },
+            );
+            conns
+        }
+        // pretend there's 9 more here
+        _ => bail!("Unknown cube net {:?}", blocks),
+    };
+    for &b in blocks.iter() {
+        let b_coord: IndexingCoordinate = b.into();
+        for f in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            assert!(
+                connections.iter().any(|(_, c)| {
+                    c.top == (b_coord, f)
+                        || c.left == (b_coord, f)
+                        || c.right == (b_coord, f)
+                        || c.bottom == (b_coord, f)
+                }),
+                "cube face {:?} has no {:?} connection",
+                b,
+                f
+            );
+        }
+    }
+    Ok(connections)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Connections {
+    top: (IndexingCoordinate, Direction),
+    left: (IndexingCoordinate, Direction),
+    right: (IndexingCoordinate, Direction),
+    bottom: (IndexingCoordinate, Direction),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day22.test.txt"))?;
+        let result = solve_part1(&input);
+        assert_eq!(result, 6032);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day22.test.txt"))?;
+        let result = solve_part2(&input, 4)?;
+        assert_eq!(result, 5031);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_grid_size() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day22.test.txt"))?;
+        let error = solve_part2(&input, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The 16x12 map doesn't split into faces of size 3"
+        );
+        let error = solve_part2(&input, 100).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The 16x12 map doesn't split into faces of size 100"
+        );
+        let error = solve_part2(&input, 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Faces of size 2 split the map into 24 faces, not 6"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw() -> color_eyre::Result<()> {
+        let text = include_str!("../../input/day22.test.txt");
+        let input = parsing::parse_input(text)?;
+        // the map comes back out, with the short rows padded to full width
+        let expected: String = text
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .map(|line| format!("{:16}\n", line))
+            .collect();
+        assert_eq!(input.map.draw(&HashMap::new()), expected);
+        let mut visited = HashMap::new();
+        visited.insert(IndexingCoordinate { row: 1, col: 9 }, Direction::East);
+        visited.insert(IndexingCoordinate { row: 1, col: 10 }, Direction::South);
+        let drawn = input.map.draw(&visited);
+        assert_eq!(drawn.lines().next(), Some("        >v.#    "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_cube() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day22.test.txt"))?;
+        let recording = Recorder::default().capture(&mut CubeWalker::new(&input, 4)?);
+        let last = recording.frames.last().unwrap();
+        assert_eq!(last.step, input.instructions.len());
+        let text = last.picture.to_text(&recording.legend);
+        // finishing facing up in row 5, column 7, as in the puzzle
+        assert_eq!(text.lines().nth(4), Some("...#..^...v#    "));
+        Ok(())
+    }
+}
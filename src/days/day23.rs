@@ -0,0 +1,229 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Coordinate, Direction, Heading, Origin, Part, Solution, SparseGrid,
+};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input<'a> = SparseGrid<i32, ()>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<i32> {
+        Ok(solve_part2(input))
+    }
+
+    fn record(
+        input: &Self::Input<'_>,
+        _params: &(),
+        part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        let recorder = match part {
+            Part::One => recorder.or_limit(10),
+            Part::Two => *recorder,
+        };
+        Ok(Some(recorder.record(&mut Elves::new(input))))
+    }
+}
+
+pub mod parsing {
+    use crate::{
+        parsers::{grid, run},
+        SparseGrid,
+    };
+    use nom::character::complete::one_of;
+
+    pub fn parse_input(input: &str) -> color_eyre::Result<SparseGrid<i32, ()>> {
+        let elves = run(grid(one_of("#.")), input)?;
+        Ok(elves
+            .iter()
+            .filter(|(_, &c)| c == '#')
+            .filter_map(|(coordinate, _)| coordinate.cast())
+            .collect())
+    }
+}
+
+const INITIAL_ORDER: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+// The elves spreading out one round at a time, see Simulation
+struct Elves {
+    elves: SparseGrid<i32, ()>,
+    // the order to consider moving in this round
+    directions: VecDeque<Direction>,
+    // rounds where someone moved
+    rounds: i32,
+}
+
+impl Elves {
+    fn new(elves: &SparseGrid<i32, ()>) -> Elves {
+        Elves {
+            elves: elves.clone(),
+            directions: INITIAL_ORDER.into_iter().collect(),
+            rounds: 0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Elf;
+
+impl Simulation for Elves {
+    type Kind = Elf;
+
+    fn step(&mut self) -> bool {
+        let new_elves = run_round(&self.elves, &self.directions);
+        if new_elves == self.elves {
+            return false;
+        }
+        self.elves = new_elves;
+        self.directions.rotate_left(1);
+        self.rounds += 1;
+        true
+    }
+
+    fn picture(&self) -> Picture<Elf> {
+        Picture::from_cells(self.elves.coordinates().map(|c| (c, Elf)), Origin::TopLeft)
+    }
+
+    fn legend(&self) -> Legend<Elf> {
+        Legend::new(Style::new('.', Rgb::WHITE, "ground"))
+            .with(Elf, Style::new('#', Rgb(40, 140, 60), "elf"))
+    }
+}
+
+pub fn solve_part1(input: &SparseGrid<i32, ()>) -> i32 {
+    let mut elves = Elves::new(input);
+    // once nobody moves, nobody ever will
+    for _ in 0..10 {
+        if !elves.step() {
+            break;
+        }
+    }
+    calculate_score(&elves.elves)
+}
+
+fn calculate_score(elves: &SparseGrid<i32, ()>) -> i32 {
+    elves.count_empty() as i32
+}
+
+fn run_round(elves: &SparseGrid<i32, ()>, directions: &VecDeque<Direction>) -> SparseGrid<i32, ()> {
+    let proposed_directions = get_proposals(elves, directions);
+    let proposed_coordinates: HashMap<Coordinate<_>, Option<Coordinate<_>>> = proposed_directions
+        .iter()
+        .map(|(c, maybe_direction)| (*c, maybe_direction.map(|d| *c + d.delta())))
+        .collect();
+    let valid_moves: HashSet<Coordinate<_>> = proposed_coordinates
+        .values()
+        .filter_map(|possible| *possible)
+        .fold(
+            HashMap::<Coordinate<_>, u32>::new(),
+            |mut total, possible| {
+                *total.entry(possible).or_default() += 1;
+                total
+            },
+        )
+        .iter()
+        .filter_map(|(c, count)| if *count == 1 { Some(*c) } else { None })
+        .collect();
+    // convert proposed into actual
+    proposed_coordinates
+        .iter()
+        .map(|(&current, proposed)| match proposed {
+            Some(p) => {
+                if valid_moves.contains(p) {
+                    *p
+                } else {
+                    current
+                }
+            }
+            None => current,
+        })
+        .collect()
+}
+
+fn get_proposals(
+    elves: &SparseGrid<i32, ()>,
+    directions: &VecDeque<Direction>,
+) -> HashMap<Coordinate<i32>, Option<Direction>> {
+    elves
+        .coordinates()
+        .map(|e| {
+            if elves.occupied_neighbours(e).next().is_none() {
+                return (e, None);
+            }
+            let possible = directions
+                .iter()
+                .find(|&&d| {
+                    // the cell straight ahead and the diagonals either side
+                    let heading = Heading::from(d);
+                    [heading.turn_left(), heading, heading.turn_right()]
+                        .iter()
+                        .all(|h| !elves.contains(e + h.delta()))
+                })
+                .copied();
+            (e, possible)
+        })
+        .collect()
+}
+
+pub fn solve_part2(input: &SparseGrid<i32, ()>) -> i32 {
+    let mut elves = Elves::new(input);
+    while elves.step() {}
+    // the first round where nobody moves
+    elves.rounds + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day23.test.txt"))?;
+        let result = solve_part1(&input);
+        assert_eq!(result, 110);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(include_str!("../../input/day23.test.txt"))?;
+        let result = solve_part2(&input);
+        assert_eq!(result, 20);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> color_eyre::Result<()> {
+        let input = parsing::parse_input(".....\n..##.\n..#..\n.....\n..##.\n.....\n")?;
+        let recording = Recorder::default().capture(&mut Elves::new(&input));
+        // the smaller example settles after 3 rounds
+        let last = recording.frames.last().unwrap();
+        assert_eq!(last.step, 3);
+        assert_eq!(
+            last.picture.to_text(&recording.legend),
+            "..#..\n....#\n#....\n....#\n.....\n..#..\n"
+        );
+        Ok(())
+    }
+}
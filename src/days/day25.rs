@@ -1,41 +1,50 @@
-use std::{collections::VecDeque, time::Instant};
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 use num::pow;
 
+use crate::Solution;
+
 #[derive(Debug, Clone)]
 pub struct Input {
     snafu: Vec<i64>,
 }
 
-fn main() -> color_eyre::Result<()> {
-    let input = parsing::parse_input(include_str!("../../input/day25.txt"))?;
-    let time = Instant::now();
-    println!(
-        "Part 1: {} in {}ms",
-        solve_part1(&input),
-        time.elapsed().as_millis()
-    );
-    Ok(())
-}
+pub struct Day25;
 
-mod parsing {
-    use super::Input;
-    use nom::{bytes::complete::take, combinator::map, multi::many1, Finish, IResult};
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const HAS_PART2: bool = false;
+    type Input<'a> = Vec<Input>;
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
 
-    fn take1(input: &str) -> IResult<&str, &str> {
-        take(1usize)(input)
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<String> {
+        Ok(solve_part1(input))
     }
+}
+
+pub mod parsing {
+    use super::Input;
+    use crate::parsers::{lines, run, IResult};
+    use nom::{character::complete::one_of, combinator::map, error::context, multi::many1};
 
     fn parse_digit(input: &str) -> IResult<&str, i64> {
-        map(take1, |c| match c {
-            "2" => 2,
-            "1" => 1,
-            "0" => 0,
-            "-" => -1,
-            "=" => -2,
-            _ => panic!("Unexpected char"),
-        })(input)
+        map(
+            context("a SNAFU digit (2, 1, 0, - or =)", one_of("210-=")),
+            |c| match c {
+                '2' => 2,
+                '1' => 1,
+                '0' => 0,
+                '-' => -1,
+                _ => -2,
+            },
+        )(input)
     }
 
     fn parse_line(input: &str) -> IResult<&str, Input> {
@@ -43,10 +52,7 @@ mod parsing {
     }
 
     pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
-        Ok(input
-            .lines()
-            .map(|line| parse_line(line).finish().unwrap().1)
-            .collect())
+        run(lines(parse_line), input)
     }
 }
 
@@ -84,7 +90,7 @@ fn decimal_to_snafu(number: i64) -> String {
     string.iter().join("")
 }
 
-fn solve_part1(input: &[Input]) -> String {
+pub fn solve_part1(input: &[Input]) -> String {
     let result: i64 = input
         .iter()
         .map(|input| snafu_to_decimal(&input.snafu))
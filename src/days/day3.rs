@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use nom::{character::complete::alpha1, error::context};
+
+use crate::{
+    parsers::{lines, run},
+    Solution,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input<'a> = Vec<&'a str>;
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<&str>> {
+    run(lines(context("a rucksack of letters", alpha1)), input)
+}
+
+pub fn solve_part1(input: &[&str]) -> u32 {
+    input
+        .iter()
+        .map(|line| {
+            let chars = line.chars().collect_vec();
+            let (first, second) = chars.split_at(chars.len() / 2);
+            let mut first_set: HashSet<char> = HashSet::new();
+            first_set.extend(first);
+            let mut second_set = HashSet::new();
+            second_set.extend(second);
+            *first_set.intersection(&second_set).next().unwrap()
+        })
+        .map(priority)
+        .sum::<u32>()
+}
+
+pub fn solve_part2(input: &[&str]) -> u32 {
+    input
+        .iter()
+        .map(|line| line.chars().collect::<HashSet<char>>())
+        .chunks(3)
+        .into_iter()
+        .map(|mut chunk| {
+            *chunk
+                .next()
+                .unwrap()
+                .intersection(&chunk.next().unwrap())
+                .copied()
+                .collect::<HashSet<char>>()
+                .intersection(&chunk.next().unwrap())
+                .next()
+                .unwrap()
+        })
+        .map(priority)
+        .sum::<u32>()
+}
+
+fn priority(c: char) -> u32 {
+    match c {
+        'a'..='z' => c as u32 - 96,
+        'A'..='Z' => c as u32 - 38,
+        _ => panic!("Invalid"),
+    }
+}
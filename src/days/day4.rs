@@ -0,0 +1,51 @@
+use crate::{parsers::each_line, Range, Solution};
+use reformation::Reformation;
+
+#[derive(Reformation, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[reformation(r"{first},{second}")]
+pub struct Input {
+    first: Range,
+    second: Range,
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input<'a> = Vec<Input>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
+    each_line(input, "two ranges like \"2-4,6-8\"", Input::parse)
+}
+
+pub fn solve_part1(input: &[Input]) -> usize {
+    input
+        .iter()
+        .filter(|input| {
+            input.first.is_subrange(&input.second) || input.second.is_subrange(&input.first)
+        })
+        .count()
+}
+
+pub fn solve_part2(input: &[Input]) -> usize {
+    input
+        .iter()
+        .filter(|input| input.first.overlap(&input.second))
+        .count()
+}
@@ -0,0 +1,453 @@
+use std::fmt::Display;
+
+use color_eyre::eyre::{bail, eyre};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, satisfy, space0, space1},
+    combinator::{map, peek, rest},
+    error::context,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use crate::{
+    parsers::{integer, lines, run, IResult, ParseError},
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Part, Solution,
+};
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    // bottom crate first, so the top crate is last
+    stacks: Vec<Vec<char>>,
+    commands: Vec<Command>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    count: u32,
+    // numbered from 1, like the drawing
+    source: usize,
+    destination: usize,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count, self.source, self.destination
+        )
+    }
+}
+
+// How a crane sets down the crates it takes off the top of a stack
+pub trait Crane {
+    // The crates come bottom first, as they were in the stack, and should be
+    // left in the order they land
+    fn carry(&self, crates: &mut [char]);
+}
+
+// Moves one crate at a time, so the top crate lands first
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn carry(&self, crates: &mut [char]) {
+        crates.reverse();
+    }
+}
+
+// Moves them all at once, so they keep their order
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn carry(&self, _crates: &mut [char]) {}
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input<'a> = Input;
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<String> {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<String> {
+        solve_part2(input)
+    }
+
+    // The stacks after each command. A command that can't be carried out
+    // ends the recording.
+    fn record(
+        input: &Self::Input<'_>,
+        _params: &(),
+        part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        let mut stacks = Stacks::new(input, crane(part));
+        let recording = recorder.record(&mut stacks);
+        match stacks.error {
+            Some(error) => Err(error.wrap_err("Recording stopped early")),
+            None => Ok(Some(recording)),
+        }
+    }
+}
+
+// "[A]" for a crate or three spaces for a gap
+fn parse_crate(input: &str) -> IResult<&str, Option<char>> {
+    context(
+        "a crate like [A] or three spaces",
+        alt((
+            map(
+                delimited(char('['), satisfy(|c| c.is_ascii_uppercase()), char(']')),
+                Some,
+            ),
+            map(tag("   "), |_| None),
+        )),
+    )(input)
+}
+
+// A row of the drawing can stop after its last crate
+fn parse_row(input: &str) -> IResult<&str, Vec<Option<char>>> {
+    separated_list1(char(' '), parse_crate)(input)
+}
+
+// " 1   2   3 ", keeping where each number is for errors
+fn parse_numbers(input: &str) -> IResult<&str, Vec<(&str, usize)>> {
+    delimited(
+        space0,
+        separated_list1(space1, pair(peek(rest), integer)),
+        space0,
+    )(input)
+}
+
+fn parse_command(input: &str) -> IResult<&str, Command> {
+    context(
+        "a command like \"move 1 from 2 to 3\"",
+        map(
+            tuple((
+                preceded(tag("move "), integer),
+                preceded(tag(" from "), integer),
+                preceded(tag(" to "), integer),
+            )),
+            |(count, source, destination)| Command {
+                count,
+                source,
+                destination,
+            },
+        ),
+    )(input)
+}
+
+// Each row with where it starts, then the stack numbers
+type Drawing<'a> = (Vec<(&'a str, Vec<Option<char>>)>, Vec<(&'a str, usize)>);
+
+fn parse_drawing(input: &str) -> IResult<&str, Drawing<'_>> {
+    pair(
+        many1(terminated(pair(peek(rest), parse_row), line_ending)),
+        terminated(parse_numbers, line_ending),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Input> {
+    // the commands start after the blank line under the drawing
+    let ((rows, numbers), commands) = run(
+        pair(terminated(parse_drawing, line_ending), lines(parse_command)),
+        input,
+    )?;
+    Ok(Input {
+        stacks: stack_crates(input, &rows, &numbers)?,
+        commands,
+    })
+}
+
+// Turn the rows of the drawing, top row first, into stacks. Errors point into
+// input at the offending crate or number.
+fn stack_crates(
+    input: &str,
+    rows: &[(&str, Vec<Option<char>>)],
+    numbers: &[(&str, usize)],
+) -> color_eyre::Result<Vec<Vec<char>>> {
+    if let Some((i, (at, _))) = numbers.iter().enumerate().find(|&(i, &(_, n))| n != i + 1) {
+        return Err(ParseError::at(input, at, format!("stack number {}", i + 1)).into());
+    }
+    let mut stacks = vec![vec![]; numbers.len()];
+    for (height, (row_at, row)) in rows.iter().rev().enumerate() {
+        if row.len() > numbers.len() {
+            let expected = format!(
+                "the end of the row, as there are only {} stacks",
+                numbers.len()
+            );
+            return Err(ParseError::at(input, crate_at(row_at, numbers.len()), expected).into());
+        }
+        for (i, (stack, &crate_)) in stacks.iter_mut().zip(row).enumerate() {
+            match crate_ {
+                Some(_) if stack.len() < height => {
+                    let expected = "a gap, as there's no crate below it";
+                    return Err(ParseError::at(input, crate_at(row_at, i), expected).into());
+                }
+                Some(c) => stack.push(c),
+                None => {}
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+// The drawing from the i-th crate along a row, as each crate or gap is three
+// characters and a space
+fn crate_at(row: &str, i: usize) -> &str {
+    &row[4 * i..]
+}
+
+fn crane(part: Part) -> &'static dyn Crane {
+    match part {
+        Part::One => &CrateMover9000,
+        Part::Two => &CrateMover9001,
+    }
+}
+
+pub fn solve_part1(input: &Input) -> color_eyre::Result<String> {
+    solve(input, crane(Part::One))
+}
+
+pub fn solve_part2(input: &Input) -> color_eyre::Result<String> {
+    solve(input, crane(Part::Two))
+}
+
+// The crate on top of each stack, leaving out any empty stacks
+pub fn solve(input: &Input, crane: &dyn Crane) -> color_eyre::Result<String> {
+    let mut stacks = Stacks::new(input, crane);
+    while stacks.apply_next()? {}
+    Ok(stacks
+        .stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .collect())
+}
+
+// The stacks as the commands are carried out one at a time
+struct Stacks<'a> {
+    stacks: Vec<Vec<char>>,
+    commands: &'a [Command],
+    next: usize,
+    crane: &'a dyn Crane,
+    // why the simulation stopped early, if a command couldn't be carried out
+    error: Option<color_eyre::Report>,
+}
+
+impl<'a> Stacks<'a> {
+    fn new(input: &'a Input, crane: &'a dyn Crane) -> Stacks<'a> {
+        Stacks {
+            stacks: input.stacks.clone(),
+            commands: &input.commands,
+            next: 0,
+            crane,
+            error: None,
+        }
+    }
+
+    // Carry out the next command, false if there are none left. The stacks
+    // are left alone if the command is impossible.
+    fn apply_next(&mut self) -> color_eyre::Result<bool> {
+        let Some(command) = self.commands.get(self.next) else {
+            return Ok(false);
+        };
+        let describe = || format!("Command {} ({})", self.next + 1, command);
+        let count = self.stacks.len();
+        let stack = |number: usize| {
+            (1..=count)
+                .contains(&number)
+                .then_some(number - 1)
+                .ok_or_else(|| {
+                    eyre!(
+                        "{} refers to stack {}, but there are only {} stacks",
+                        describe(),
+                        number,
+                        count
+                    )
+                })
+        };
+        let (source, destination) = (stack(command.source)?, stack(command.destination)?);
+        let available = self.stacks[source].len();
+        let Some(remaining) = available.checked_sub(command.count as usize) else {
+            bail!(
+                "{} can't move {} crates from stack {}, which only has {}",
+                describe(),
+                command.count,
+                command.source,
+                available
+            );
+        };
+        let mut crates = self.stacks[source].split_off(remaining);
+        self.crane.carry(&mut crates);
+        self.stacks[destination].extend(crates);
+        self.next += 1;
+        Ok(true)
+    }
+}
+
+// What the drawing shows in each cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Open,
+    Close,
+    Crate(char),
+}
+
+impl Simulation for Stacks<'_> {
+    type Kind = Cell;
+
+    fn step(&mut self) -> bool {
+        match self.apply_next() {
+            Ok(more) => more,
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+
+    // Drawn like the puzzle input, without the stack numbers
+    fn picture(&self) -> Picture<Cell> {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut picture = Picture::new((4 * self.stacks.len()).saturating_sub(1), height);
+        for (i, stack) in self.stacks.iter().enumerate() {
+            for (level, &c) in stack.iter().enumerate() {
+                let row = height - 1 - level;
+                picture.set(row, 4 * i, Cell::Open);
+                picture.set(row, 4 * i + 1, Cell::Crate(c));
+                picture.set(row, 4 * i + 2, Cell::Close);
+            }
+        }
+        picture
+    }
+
+    fn legend(&self) -> Legend<Cell> {
+        let crates = self.stacks.iter().flatten().copied().unique().sorted();
+        crates.fold(
+            Legend::new(Style::new(' ', Rgb::WHITE, "empty"))
+                .with(Cell::Open, Style::new('[', Rgb(120, 80, 40), "crate"))
+                .with(Cell::Close, Style::new(']', Rgb(120, 80, 40), "crate")),
+            |legend, c| {
+                legend.with(
+                    Cell::Crate(c),
+                    Style::new(c, Rgb(200, 150, 90), &format!("crate {}", c)),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day5.test.txt"))?;
+        assert_eq!(
+            input.stacks,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(input.commands.len(), 4);
+        // rows can stop short, and stacks can be numbered past 9
+        let input = parse_input(
+            "[A]\n[B]                                 [C]\n 1   2   3   4   5   6   7   8   9   10\n\nmove 1 from 10 to 2\n",
+        )?;
+        assert_eq!(input.stacks[0], vec!['B', 'A']);
+        assert_eq!(input.stacks[9], vec!['C']);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_drawing() {
+        let error = parse_input("[A]\n    [B]\n 1   2\n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 1, column 1: expected a gap, as there's no crate below it but found \"[A]\""
+        );
+        let error = parse_input("[A] [B]\n 1\n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 1, column 5: expected the end of the row, as there are only 1 stacks but found \"[B]\""
+        );
+        let error = parse_input("[A] [B]\n 1   3\n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 6: expected stack number 2 but found \"3\""
+        );
+    }
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day5.test.txt"))?;
+        assert_eq!(solve_part1(&input)?, "CMZ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day5.test.txt"))?;
+        assert_eq!(solve_part2(&input)?, "MCD");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_command() -> color_eyre::Result<()> {
+        let mut input = parse_input(include_str!("../../input/day5.test.txt"))?;
+        // stack 1 has 3 crates after the first command, so the second can't take 4
+        input.commands[1].count = 4;
+        assert_eq!(
+            solve(&input, &CrateMover9001).unwrap_err().to_string(),
+            "Command 2 (move 4 from 1 to 3) can't move 4 crates from stack 1, which only has 3"
+        );
+        input.commands[1].destination = 4;
+        assert_eq!(
+            solve(&input, &CrateMover9001).unwrap_err().to_string(),
+            "Command 2 (move 4 from 1 to 4) refers to stack 4, but there are only 3 stacks"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day5.test.txt"))?;
+        let recording = Recorder::default().capture(&mut Stacks::new(&input, &CrateMover9000));
+        let text = recording
+            .frames
+            .iter()
+            .map(|frame| frame.picture.to_text(&recording.legend))
+            .collect_vec();
+        // the starting drawing, then after each command
+        assert_eq!(text[0], "    [D]    \n[N] [C]    \n[Z] [M] [P]\n");
+        assert_eq!(text[1], "[D]        \n[N] [C]    \n[Z] [M] [P]\n");
+        assert_eq!(
+            text[4],
+            "        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n"
+        );
+        // a bad command stops the recording with its error, rather than
+        // looking like the end of the rearrangement
+        let input =
+            parse_input("[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 1 to 2\nmove 3 from 2 to 1\n")?;
+        let error = Day5::record(&input, &(), Part::One, &Recorder::default()).unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "Recording stopped early: Command 2 (move 3 from 2 to 1) can't move 3 \
+             crates from stack 2, which only has 2"
+        );
+        Ok(())
+    }
+}
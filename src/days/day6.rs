@@ -0,0 +1,165 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufReader, Read},
+};
+
+use color_eyre::eyre::eyre;
+use nom::{character::complete::alpha1, error::context};
+
+use crate::{parsers::run, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input<'a> = &'a str;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        solve_part2(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<&str> {
+    run(context("letters", alpha1), input)
+}
+
+pub const PACKET: usize = 4;
+pub const MESSAGE: usize = 14;
+
+pub fn solve_part1(input: &str) -> color_eyre::Result<usize> {
+    first_marker(input.as_bytes(), PACKET)
+}
+
+pub fn solve_part2(input: &str) -> color_eyre::Result<usize> {
+    first_marker(input.as_bytes(), MESSAGE)
+}
+
+// Where the first marker ends, reading only as far as it
+pub fn first_marker<R: Read>(reader: R, window: usize) -> color_eyre::Result<usize> {
+    read_markers(reader, window)
+        .next()
+        .transpose()?
+        .ok_or_else(|| eyre!("There's no marker of {} different characters", window))
+}
+
+// How many bytes have been read at the end of each marker, where the last
+// window bytes are all different. The signal is read as it goes, so it never
+// has to fit in memory. It ends at the end of the first line.
+pub fn read_markers<R: Read>(reader: R, window: usize) -> impl Iterator<Item = io::Result<usize>> {
+    let mut detector = MarkerDetector::new(window);
+    BufReader::new(reader)
+        .bytes()
+        .take_while(|byte| !matches!(byte, Ok(b'\n' | b'\r')))
+        .filter_map(move |byte| match byte {
+            Ok(byte) => detector.push(byte).map(Ok),
+            Err(e) => Some(Err(e)),
+        })
+}
+
+// Keeps only the last window bytes and how often each value appears in them,
+// so each byte takes the same work however long the signal or the window
+pub struct MarkerDetector {
+    window: usize,
+    recent: VecDeque<u8>,
+    counts: [usize; 256],
+    // values that appear more than once in recent
+    repeated: usize,
+    read: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(window: usize) -> MarkerDetector {
+        assert!(window > 0, "A marker has to be at least one byte long");
+        MarkerDetector {
+            window,
+            recent: VecDeque::with_capacity(window),
+            counts: [0; 256],
+            repeated: 0,
+            read: 0,
+        }
+    }
+
+    // Add the next byte, returning how many have been read if it ends a marker
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        self.read += 1;
+        if self.recent.len() == self.window {
+            let oldest = self.recent.pop_front().unwrap() as usize;
+            self.counts[oldest] -= 1;
+            if self.counts[oldest] == 1 {
+                self.repeated -= 1;
+            }
+        }
+        self.recent.push_back(byte);
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+        (self.recent.len() == self.window && self.repeated == 0).then_some(self.read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        for (input, expected, _) in EXAMPLES {
+            assert_eq!(solve_part1(parse_input(input)?)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        for (input, _, expected) in EXAMPLES {
+            assert_eq!(solve_part2(parse_input(input)?)?, expected);
+        }
+        assert!(solve_part2("abcabc").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_markers() -> color_eyre::Result<()> {
+        let found = read_markers("aabcbb".as_bytes(), 2).collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(found, vec![3, 4, 5]);
+        let found = read_markers("aabcbb".as_bytes(), 3).collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(found, vec![4]);
+        // the line ending isn't part of the signal
+        let found = read_markers("abca\nb".as_bytes(), 4).collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(found, vec![]);
+        assert_eq!(first_marker("aabcd\n".as_bytes(), PACKET)?, 5);
+        let error = first_marker("abca\n".as_bytes(), PACKET).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "There's no marker of 4 different characters"
+        );
+        // a byte at a time, only ever holding the window
+        let mut detector = MarkerDetector::new(4);
+        let signal = b"abcd".iter().copied().cycle().take(1_000_000);
+        assert_eq!(
+            signal.filter_map(|byte| detector.push(byte)).count(),
+            1_000_000 - 3
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,732 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Write},
+};
+
+use color_eyre::eyre::{bail, eyre};
+use itertools::Itertools;
+use reformation::Reformation;
+
+use crate::{parse_param, parsers::each_line, Solution};
+
+#[derive(Debug, Reformation, Clone)]
+#[reformation()]
+pub enum Command {
+    #[reformation(r"\$ cd {}")]
+    Cd(String),
+    #[reformation(r"\$ ls")]
+    Ls,
+    #[reformation(r"dir {}")]
+    Dir(String),
+    #[reformation(r"{} {}")]
+    File(u64, String),
+}
+
+// How much space the update needs, for part 2
+pub struct Params {
+    pub capacity: u64,
+    pub required_free: u64,
+    // delete whichever directories free the least space between them, rather
+    // than a single directory
+    pub minimum_set: bool,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            capacity: 70_000_000,
+            required_free: 30_000_000,
+            minimum_set: false,
+        }
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input<'a> = FileSystem;
+    type Params = Params;
+    type Part1 = u64;
+    type Part2 = Deletion;
+
+    fn set_param(params: &mut Params, name: &str, value: &str) -> color_eyre::Result<()> {
+        match name {
+            "capacity" => params.capacity = parse_param(name, value)?,
+            "required_free" => params.required_free = parse_param(name, value)?,
+            "minimum_set" => params.minimum_set = parse_param(name, value)?,
+            _ => bail!("Day 7 has no parameter {}", name),
+        }
+        Ok(())
+    }
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        let fs = FileSystem::from_log(&parse_input(input)?);
+        for problem in fs.problems() {
+            log::warn!("Day 7 terminal log: {}", problem);
+        }
+        log::debug!("Day 7 filesystem:\n{}", fs.tree());
+        Ok(fs)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &Params) -> color_eyre::Result<u64> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, params: &Params) -> color_eyre::Result<Deletion> {
+        if params.minimum_set {
+            plan_minimum_deletion(input, params)
+        } else {
+            plan_deletion(input, params)
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Command>> {
+    each_line(input, "a command or a directory listing", Command::parse)
+}
+
+// What ls showed in a directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Listing {
+    dirs: BTreeSet<String>,
+    files: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Directory {
+    // None until it's been listed
+    listing: Option<Listing>,
+    // every directory known to be inside, whether listed or changed into
+    children: BTreeSet<String>,
+}
+
+// Something in the terminal log that doesn't add up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    // listed again with different contents, the first listing is kept
+    ListedTwice(String),
+    // changed into, but its parent's listing didn't include it
+    NotInListing(String),
+    // never listed, so whatever is inside is missing from the sizes
+    NeverListed(String),
+    // cd .. from /, which stays put
+    AboveRoot,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::ListedTwice(path) => {
+                write!(f, "{} was listed twice with different contents", path)
+            }
+            Problem::NotInListing(path) => write!(
+                f,
+                "{} was changed into but isn't in its parent's listing",
+                path
+            ),
+            Problem::NeverListed(path) => {
+                write!(f, "{} was never listed, so its size is unknown", path)
+            }
+            Problem::AboveRoot => write!(f, "cd .. was used in /"),
+        }
+    }
+}
+
+// A file or directory, with the total size of everything inside a directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+}
+
+impl Entry {
+    fn name(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((_, "")) | None => "/",
+            Some((_, name)) => name,
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.path.matches('/').count() - usize::from(self.path == "/")
+    }
+}
+
+// What to look for with FileSystem::find
+pub enum Find<'a> {
+    Name(&'a str),
+    MinSize(u64),
+}
+
+// The directories and files seen in a terminal log, by absolute path
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    dirs: BTreeMap<String, Directory>,
+    problems: Vec<Problem>,
+}
+
+impl FileSystem {
+    pub fn from_log(commands: &[Command]) -> FileSystem {
+        let mut fs = FileSystem {
+            dirs: BTreeMap::from([("/".to_owned(), Directory::default())]),
+            problems: vec![],
+        };
+        // path components of the working directory
+        let mut cwd = vec![];
+        let mut listing = None;
+        for command in commands {
+            match command {
+                Command::Cd(path) => {
+                    fs.add_listing(&cwd, listing.take());
+                    fs.change_dir(&mut cwd, path);
+                }
+                Command::Ls => {
+                    fs.add_listing(&cwd, listing.take());
+                    listing = Some(Listing::default());
+                }
+                Command::Dir(name) => {
+                    listing
+                        .get_or_insert_with(Listing::default)
+                        .dirs
+                        .insert(name.clone());
+                }
+                Command::File(size, name) => {
+                    listing
+                        .get_or_insert_with(Listing::default)
+                        .files
+                        .insert(name.clone(), *size);
+                }
+            }
+        }
+        fs.add_listing(&cwd, listing);
+        // what's missing can only be known once the whole log has been read
+        let mut problems = vec![];
+        for (path, dir) in &fs.dirs {
+            if let Some(listing) = &dir.listing {
+                let missing = dir.children.difference(&listing.dirs);
+                problems.extend(missing.map(|name| Problem::NotInListing(join(path, name))));
+            }
+        }
+        for (path, dir) in &fs.dirs {
+            if dir.listing.is_none() {
+                problems.push(Problem::NeverListed(path.clone()));
+            }
+        }
+        fs.problems.extend(problems);
+        fs
+    }
+
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    fn report(&mut self, problem: Problem) {
+        if !self.problems.contains(&problem) {
+            self.problems.push(problem);
+        }
+    }
+
+    fn add_dir(&mut self, parent: &str, name: &str) {
+        self.dirs.entry(join(parent, name)).or_default();
+        self.dirs
+            .get_mut(parent)
+            .unwrap()
+            .children
+            .insert(name.to_owned());
+    }
+
+    fn add_listing(&mut self, cwd: &[String], listing: Option<Listing>) {
+        let Some(listing) = listing else {
+            return;
+        };
+        let path = to_path(cwd);
+        match &self.dirs[&path].listing {
+            Some(existing) if *existing != listing => self.report(Problem::ListedTwice(path)),
+            Some(_) => {}
+            None => {
+                for name in &listing.dirs {
+                    self.add_dir(&path, name);
+                }
+                self.dirs.get_mut(&path).unwrap().listing = Some(listing);
+            }
+        }
+    }
+
+    // Follow a cd, which can be absolute and go through several directories
+    fn change_dir(&mut self, cwd: &mut Vec<String>, path: &str) {
+        if path.starts_with('/') {
+            cwd.clear();
+        }
+        for part in path
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+        {
+            if part == ".." {
+                if cwd.pop().is_none() {
+                    self.report(Problem::AboveRoot);
+                }
+                continue;
+            }
+            self.add_dir(&to_path(cwd), part);
+            cwd.push(part.to_owned());
+        }
+    }
+
+    // Every file and directory, each directory followed by what's inside it in
+    // name order
+    pub fn entries(&self) -> Vec<Entry> {
+        let mut entries = vec![];
+        self.walk("/", &mut entries);
+        entries
+    }
+
+    // Adds the directory and everything in it, returning its size
+    fn walk(&self, path: &str, entries: &mut Vec<Entry>) -> u64 {
+        let index = entries.len();
+        entries.push(Entry {
+            path: path.to_owned(),
+            size: 0,
+            is_dir: true,
+        });
+        let dir = &self.dirs[path];
+        let files = dir.listing.iter().flat_map(|listing| &listing.files);
+        let mut names = dir
+            .children
+            .iter()
+            .map(|name| (name, None))
+            .chain(files.map(|(name, &size)| (name, Some(size))))
+            .collect::<Vec<_>>();
+        names.sort();
+        let mut size = 0;
+        for (name, file_size) in names {
+            let path = join(path, name);
+            size += match file_size {
+                Some(file_size) => {
+                    entries.push(Entry {
+                        path,
+                        size: file_size,
+                        is_dir: false,
+                    });
+                    file_size
+                }
+                None => self.walk(&path, entries),
+            };
+        }
+        entries[index].size = size;
+        size
+    }
+
+    // The size of a file, or of everything in a directory
+    pub fn du(&self, path: &str) -> Option<u64> {
+        let path = to_path(&components(path));
+        self.entries()
+            .into_iter()
+            .find(|entry| entry.path == path)
+            .map(|entry| entry.size)
+    }
+
+    pub fn find(&self, find: Find) -> Vec<Entry> {
+        self.entries()
+            .into_iter()
+            .filter(|entry| match find {
+                Find::Name(name) => entry.name() == name,
+                Find::MinSize(size) => entry.size >= size,
+            })
+            .collect()
+    }
+
+    // Like the puzzle draws it, with the size of each directory too
+    pub fn tree(&self) -> String {
+        let mut tree = String::new();
+        for entry in self.entries() {
+            let kind = if entry.is_dir { "dir" } else { "file" };
+            // writing to a String can't fail
+            let _ = writeln!(
+                tree,
+                "{}- {} ({}, size={})",
+                "  ".repeat(entry.depth()),
+                entry.name(),
+                kind,
+                entry.size
+            );
+        }
+        tree
+    }
+}
+
+fn components(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn to_path(components: &[String]) -> String {
+    format!("/{}", components.join("/"))
+}
+
+fn join(parent: &str, name: &str) -> String {
+    match parent {
+        "/" => format!("/{}", name),
+        parent => format!("{}/{}", parent, name),
+    }
+}
+
+pub fn solve_part1(fs: &FileSystem) -> u64 {
+    fs.entries()
+        .iter()
+        .filter(|entry| entry.is_dir && entry.size <= 100_000)
+        .map(|entry| entry.size)
+        .sum()
+}
+
+// The directories to delete and the space that frees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deletion {
+    pub paths: Vec<String>,
+    pub freed: u64,
+}
+
+impl Display for Deletion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.paths.as_slice() {
+            [] => write!(f, "there's already enough free space"),
+            paths => write!(f, "delete {} to free {}", paths.join(", "), self.freed),
+        }
+    }
+}
+
+// How much more space has to be freed
+fn space_needed(fs: &FileSystem, params: &Params) -> color_eyre::Result<u64> {
+    if params.required_free > params.capacity {
+        bail!(
+            "The update needs {} free but the disk only holds {}",
+            params.required_free,
+            params.capacity
+        );
+    }
+    let used = fs.du("/").unwrap_or(0);
+    Ok((used + params.required_free).saturating_sub(params.capacity))
+}
+
+// The directories at least this big
+fn dirs_at_least(fs: &FileSystem, size: u64) -> Vec<Entry> {
+    fs.find(Find::MinSize(size))
+        .into_iter()
+        .filter(|entry| entry.is_dir)
+        .collect()
+}
+
+// The smallest directory that frees up enough space
+pub fn plan_deletion(fs: &FileSystem, params: &Params) -> color_eyre::Result<Deletion> {
+    let needed = space_needed(fs, params)?;
+    if needed == 0 {
+        return Ok(Deletion {
+            paths: vec![],
+            freed: 0,
+        });
+    }
+    dirs_at_least(fs, needed)
+        .into_iter()
+        .min_by_key(|dir| dir.size)
+        .map(|dir| Deletion {
+            paths: vec![dir.path],
+            freed: dir.size,
+        })
+        .ok_or_else(|| eyre!("Deleting everything still doesn't free {}", needed))
+}
+
+// The directories that free up enough space while deleting as little as
+// possible. There's no point deleting a directory inside another one that's
+// going, so the directories are all separate.
+pub fn plan_minimum_deletion(fs: &FileSystem, params: &Params) -> color_eyre::Result<Deletion> {
+    let mut best = plan_deletion(fs, params)?;
+    let needed = space_needed(fs, params)?;
+    // anything as big as the best single directory can't be part of a better
+    // set, and the biggest are tried first to get close to needed quickly
+    let mut candidates = dirs_at_least(fs, 1)
+        .into_iter()
+        .filter(|dir| dir.size < best.freed)
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|dir| Reverse(dir.size));
+    // the most the candidates from each index on could free, ignoring that
+    // some are inside others
+    let mut available = candidates
+        .iter()
+        .rev()
+        .scan(0, |total, dir| {
+            *total += dir.size;
+            Some(*total)
+        })
+        .collect::<Vec<_>>();
+    available.reverse();
+    let mut chosen = vec![];
+    find_deletion(
+        &candidates,
+        &available,
+        needed,
+        0,
+        0,
+        &mut chosen,
+        &mut best,
+    );
+    Ok(best)
+}
+
+// Try adding each candidate from start on to the chosen directories, keeping
+// the best deletion found
+fn find_deletion<'a>(
+    candidates: &'a [Entry],
+    available: &[u64],
+    needed: u64,
+    start: usize,
+    freed: u64,
+    chosen: &mut Vec<&'a Entry>,
+    best: &mut Deletion,
+) {
+    if freed >= needed {
+        if freed < best.freed {
+            *best = Deletion {
+                paths: chosen.iter().map(|dir| dir.path.clone()).sorted().collect(),
+                freed,
+            };
+        }
+        return;
+    }
+    for (i, dir) in candidates.iter().enumerate().skip(start) {
+        if freed + available[i] < needed || best.freed == needed {
+            return;
+        }
+        let overlaps = chosen
+            .iter()
+            .any(|other| contains(&other.path, &dir.path) || contains(&dir.path, &other.path));
+        if freed + dir.size >= best.freed || overlaps {
+            continue;
+        }
+        chosen.push(dir);
+        find_deletion(
+            candidates,
+            available,
+            needed,
+            i + 1,
+            freed + dir.size,
+            chosen,
+            best,
+        );
+        chosen.pop();
+    }
+}
+
+// Whether path is inside the directory
+fn contains(dir: &str, path: &str) -> bool {
+    dir == "/"
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, Puzzle};
+
+    // 110 used between /, /a, /a/n, /b and /c
+    const NESTED: &str = "\
+$ cd /
+$ ls
+dir a
+dir b
+dir c
+5 x
+$ cd a
+$ ls
+dir n
+10 y
+$ cd n
+$ ls
+40 z
+$ cd /b
+$ ls
+30 z
+$ cd /c
+$ ls
+25 z
+";
+
+    fn example() -> color_eyre::Result<FileSystem> {
+        let commands = parse_input(include_str!("../../input/day7.test.txt"))?;
+        Ok(FileSystem::from_log(&commands))
+    }
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let result = solve_part1(&example()?);
+        assert_eq!(result, 95437);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let deletion = plan_deletion(&example()?, &Params::default())?;
+        assert_eq!(
+            deletion,
+            Deletion {
+                paths: vec!["/d".to_owned()],
+                freed: 24933642
+            }
+        );
+        assert_eq!(deletion.to_string(), "delete /d to free 24933642");
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_deletion() -> color_eyre::Result<()> {
+        let commands = parse_input(NESTED)?;
+        let fs = FileSystem::from_log(&commands);
+        // 110 used, so 75 has to go
+        let mut params = Params {
+            capacity: 100,
+            required_free: 65,
+            minimum_set: true,
+        };
+        assert_eq!(plan_deletion(&fs, &params)?.paths, ["/"]);
+        let deletion = plan_minimum_deletion(&fs, &params)?;
+        assert_eq!(deletion.paths, ["/a", "/c"]);
+        assert_eq!(deletion.to_string(), "delete /a, /c to free 75");
+        // /a and /a/n make 90 between them, but that's counting /a/n twice
+        params.required_free = 80;
+        let deletion = plan_minimum_deletion(&fs, &params)?;
+        assert_eq!(deletion.paths, ["/a/n", "/b", "/c"]);
+        assert_eq!(deletion.freed, 95);
+        // already enough room
+        params.capacity = 200;
+        params.required_free = 90;
+        assert_eq!(plan_minimum_deletion(&fs, &params)?.freed, 0);
+        params.required_free = 300;
+        assert!(plan_deletion(&fs, &params).is_err());
+        assert!(plan_minimum_deletion(&fs, &params).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_params() -> color_eyre::Result<()> {
+        let params = [
+            ("capacity", "100"),
+            ("required_free", "65"),
+            ("minimum_set", "true"),
+        ]
+        .map(|(name, value)| (name.to_owned(), value.to_owned()));
+        let input = NESTED;
+        // 75 has to go
+        let timed = Day7.run_with(Part::Two, input, false, &params)?;
+        assert_eq!(timed.answer, "delete /a, /c to free 75");
+        let timed = Day7.run_with(Part::Two, input, false, &params[..2])?;
+        assert_eq!(timed.answer, "delete / to free 110");
+        let error = Day7
+            .run_with(
+                Part::Two,
+                input,
+                false,
+                &[("size".to_owned(), "1".to_owned())],
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Day 7 has no parameter size");
+        let error = Day7
+            .run_with(
+                Part::Two,
+                input,
+                false,
+                &[("minimum_set".to_owned(), "yes".to_owned())],
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value yes for parameter minimum_set"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_queries() -> color_eyre::Result<()> {
+        let fs = example()?;
+        assert!(fs.problems().is_empty());
+        assert_eq!(fs.du("/"), Some(48381165));
+        assert_eq!(fs.du("/a/e/"), Some(584));
+        assert_eq!(fs.du("/d/k"), Some(7214296));
+        assert_eq!(fs.du("/x"), None);
+        let found = fs.find(Find::Name("e"));
+        assert_eq!(
+            found,
+            vec![Entry {
+                path: "/a/e".to_owned(),
+                size: 584,
+                is_dir: true
+            }]
+        );
+        let big = fs.find(Find::MinSize(8_000_000));
+        let paths = big
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/", "/b.txt", "/c.dat", "/d", "/d/d.log"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree() -> color_eyre::Result<()> {
+        let expected = "\
+- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+";
+        assert_eq!(example()?.tree(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_problems() -> color_eyre::Result<()> {
+        let log = "\
+$ cd /
+$ ls
+dir a
+1 x
+$ cd /a/b
+$ cd ..
+$ ls
+2 y
+$ cd /
+$ ls
+dir a
+2 x
+$ cd ..
+";
+        let fs = FileSystem::from_log(&parse_input(log)?);
+        assert_eq!(
+            fs.problems(),
+            [
+                Problem::ListedTwice("/".to_owned()),
+                Problem::AboveRoot,
+                Problem::NotInListing("/a/b".to_owned()),
+                Problem::NeverListed("/a/b".to_owned()),
+            ]
+        );
+        // the first listing of / is kept, and /a/b counts for nothing
+        assert_eq!(fs.du("/"), Some(3));
+        assert_eq!(fs.du("/a"), Some(2));
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Coordinate, Grid, Solution};
+use itertools::Itertools;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input<'a> = Grid<u32>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u64> {
+        Ok(solve_part2(input))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Grid<u32>> {
+    Grid::parse(input, "a tree height", |c| c.to_digit(10))
+}
+
+pub fn solve_part1(heights: &Grid<u32>) -> usize {
+    heights
+        .coordinates()
+        .filter(|&coord| is_visible(heights, coord))
+        .count()
+}
+
+pub fn solve_part2(heights: &Grid<u32>) -> u64 {
+    heights
+        .coordinates()
+        .map(|coord| get_scenic_score(heights, coord))
+        .max()
+        // no trees, so no view from any of them
+        .unwrap_or(0)
+}
+
+// Heights looking up, down, left and right from the tree, nearest first
+fn get_lines_of_sight(heights: &Grid<u32>, coord: Coordinate<usize>) -> [Vec<u32>; 4] {
+    let row = heights.row(coord.row);
+    let column = heights.column(coord.col).copied().collect_vec();
+    [
+        column[..coord.row].iter().rev().copied().collect_vec(),
+        column[coord.row + 1..].to_vec(),
+        row[..coord.col].iter().rev().copied().collect_vec(),
+        row[coord.col + 1..].to_vec(),
+    ]
+}
+
+fn is_visible(heights: &Grid<u32>, coord: Coordinate<usize>) -> bool {
+    // search row and column for higher things
+    let tree_height = heights[coord];
+    get_lines_of_sight(heights, coord)
+        .iter()
+        .any(|line| line.iter().all(|&height| height < tree_height))
+}
+
+fn get_scenic_score(heights: &Grid<u32>, coord: Coordinate<usize>) -> u64 {
+    let tree_height = heights[coord];
+    get_lines_of_sight(heights, coord)
+        .iter()
+        .map(|line| get_direction_scenic_score(line, |&height| height >= tree_height))
+        .product()
+}
+
+fn get_direction_scenic_score<F>(line: &[u32], predicate: F) -> u64
+where
+    F: FnMut(&u32) -> bool,
+{
+    line.split_inclusive(predicate)
+        .next()
+        .map_or(0, |slice| slice.len()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day8.test.txt"))?;
+        let result = solve_part1(&input);
+        assert_eq!(result, 21);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day8.test.txt"))?;
+        let result = solve_part2(&input);
+        assert_eq!(result, 8);
+        assert_eq!(solve_part2(&Grid::from_rows(vec![])?), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use crate::{
+    parsers::{integer, lines, run, IResult},
+    render::{Legend, Picture, Rgb, Style},
+    simulation::{Recorder, Simulation},
+    Coordinate, Direction, Origin, Part, Solution,
+};
+use nom::{
+    character::complete::{anychar, char},
+    combinator::{map, map_res},
+    error::context,
+    sequence::separated_pair,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Motion {
+    direction: Direction,
+    distance: u32,
+}
+
+impl Motion {
+    // Up moves the head to a higher row
+    fn step(&self) -> Coordinate<i32> {
+        self.direction.delta_in(Origin::BottomLeft)
+    }
+}
+
+// The rope one step of the head at a time, see Simulation
+#[derive(Debug)]
+struct Rope<'a> {
+    motions: &'a [Motion],
+    // the motion being made and how far along it the head has got
+    motion: usize,
+    moved: u32,
+    // the head first and the tail last
+    knots: Vec<Coordinate<i32>>,
+    visited: HashSet<Coordinate<i32>>,
+}
+
+impl<'a> Rope<'a> {
+    fn new(motions: &'a [Motion], knots: usize) -> Rope<'a> {
+        Rope {
+            motions,
+            motion: 0,
+            moved: 0,
+            knots: vec![Coordinate { row: 0, col: 0 }; knots],
+            visited: vec![Coordinate { row: 0, col: 0 }].into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Start,
+    Visited,
+    // by position along the rope, so 0 is the head
+    Knot(usize),
+}
+
+impl Simulation for Rope<'_> {
+    type Kind = Cell;
+
+    fn step(&mut self) -> bool {
+        let motion = loop {
+            let Some(motion) = self.motions.get(self.motion) else {
+                return false;
+            };
+            if self.moved < motion.distance {
+                break motion;
+            }
+            self.motion += 1;
+            self.moved = 0;
+        };
+        self.moved += 1;
+        self.knots[0] += motion.step();
+        for i in 1..self.knots.len() {
+            self.knots[i] = resolve_tail_position(self.knots[i - 1], self.knots[i]);
+        }
+        self.visited.insert(*self.knots.last().unwrap());
+        true
+    }
+
+    // Knots nearer the head cover the ones behind them, like the puzzle draws it
+    fn picture(&self) -> Picture<Cell> {
+        let visited = self.visited.iter().map(|&c| (c, Cell::Visited));
+        let start = std::iter::once((Coordinate { row: 0, col: 0 }, Cell::Start));
+        let knots = self
+            .knots
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, &c)| (c, Cell::Knot(i)));
+        Picture::from_cells(visited.chain(start).chain(knots), Origin::BottomLeft)
+    }
+
+    fn legend(&self) -> Legend<Cell> {
+        let tail = self.knots.len() - 1;
+        (0..=tail).fold(
+            Legend::new(Style::new('.', Rgb::WHITE, "unvisited"))
+                .with(
+                    Cell::Visited,
+                    Style::new('#', Rgb(160, 200, 240), "visited by the tail"),
+                )
+                .with(Cell::Start, Style::new('s', Rgb(60, 160, 60), "start")),
+            |legend, i| {
+                let style = match i {
+                    0 => Style::new('H', Rgb(200, 60, 40), "head"),
+                    i if i == tail => Style::new('T', Rgb(40, 60, 200), "tail"),
+                    i => Style::new(
+                        char::from_digit(i as u32, 10).unwrap_or('k'),
+                        Rgb(120, 60, 160),
+                        &format!("knot {}", i),
+                    ),
+                };
+                legend.with(Cell::Knot(i), style)
+            },
+        )
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input<'a> = Vec<Motion>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<usize> {
+        Ok(solve_part2(input))
+    }
+
+    fn record(
+        input: &Self::Input<'_>,
+        _params: &(),
+        part: Part,
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        let knots = match part {
+            Part::One => 2,
+            Part::Two => 10,
+        };
+        Ok(Some(recorder.record(&mut Rope::new(input, knots))))
+    }
+}
+
+pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Motion>> {
+    run(lines(parse_motion), input)
+}
+
+fn parse_motion(input: &str) -> IResult<&str, Motion> {
+    map(
+        separated_pair(
+            context(
+                "a direction (U/D/L/R)",
+                map_res(anychar, Direction::try_from),
+            ),
+            char(' '),
+            integer,
+        ),
+        |(direction, distance)| Motion {
+            direction,
+            distance,
+        },
+    )(input)
+}
+
+pub fn solve_part1(input: &[Motion]) -> usize {
+    tail_visits(input, 2)
+}
+
+fn tail_visits(input: &[Motion], knots: usize) -> usize {
+    let mut rope = Rope::new(input, knots);
+    while rope.step() {}
+    rope.visited.len()
+}
+
+fn resolve_tail_position(
+    head_position: Coordinate<i32>,
+    tail_position: Coordinate<i32>,
+) -> Coordinate<i32> {
+    if head_position.chebyshev(&tail_position) <= 1 {
+        tail_position
+    } else {
+        // step one towards the head on each axis that differs
+        tail_position + (head_position - tail_position).signum()
+    }
+}
+
+pub fn solve_part2(input: &[Motion]) -> usize {
+    tail_visits(input, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("R 4\nX 2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 1: expected a direction (U/D/L/R) but found \"X 2\""
+        );
+    }
+
+    #[test]
+    fn test_part1() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day9.test.txt"))?;
+        let result = solve_part1(&input);
+        dbg!(result);
+        assert!(result == 13);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day9.test.txt"))?;
+        let result = solve_part2(&input);
+        assert!(result == 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_2() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day9.test.2.txt"))?;
+        let result = solve_part2(&input);
+        assert!(result == 36);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> color_eyre::Result<()> {
+        let input = parse_input(include_str!("../../input/day9.test.txt"))?;
+        let recorder = Recorder::default().or_limit(4);
+        let recording = recorder.capture(&mut Rope::new(&input, 10));
+        // after R 4, with the rest of the rope still piled up on the start
+        let last = recording.frames.last().unwrap();
+        assert_eq!(last.picture.to_text(&recording.legend), "4321H\n");
+        Ok(())
+    }
+}
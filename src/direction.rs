@@ -0,0 +1,260 @@
+use std::{fmt::Display, str::FromStr};
+
+use color_eyre::eyre::{bail, eyre};
+use num::{PrimInt, Signed};
+
+use crate::{Coordinate, Origin};
+
+// One of the four ways to move along a row or column
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    // Clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_around(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    // One step in this direction when rows grow down the page, so north is (-1, 0)
+    pub fn delta<T: PrimInt + Signed>(self) -> Coordinate<T> {
+        self.delta_in(Origin::TopLeft)
+    }
+
+    // One step in this direction, with north going up whichever way rows grow
+    pub fn delta_in<T: PrimInt + Signed>(self, origin: Origin) -> Coordinate<T> {
+        Heading::from(self).delta_in(origin)
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+// Accepts U/D/L/R, N/S/E/W and arrows
+impl TryFrom<char> for Direction {
+    type Error = color_eyre::Report;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::North),
+            'R' | 'E' | '>' => Ok(Direction::East),
+            'D' | 'S' | 'v' => Ok(Direction::South),
+            'L' | 'W' | '<' => Ok(Direction::West),
+            _ => Err(eyre!("Invalid direction {:?}", c)),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(eyre!("Invalid direction {:?}", s)),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Heading::from(*self).fmt(f)
+    }
+}
+
+// The four cardinal directions plus the four ordinal ones between them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Heading {
+    // Clockwise from north
+    pub const ALL: [Heading; 8] = [
+        Heading::North,
+        Heading::NorthEast,
+        Heading::East,
+        Heading::SouthEast,
+        Heading::South,
+        Heading::SouthWest,
+        Heading::West,
+        Heading::NorthWest,
+    ];
+
+    // An eighth of a turn
+    pub fn turn_right(self) -> Heading {
+        Heading::ALL[(self as usize + 1) % 8]
+    }
+
+    // An eighth of a turn
+    pub fn turn_left(self) -> Heading {
+        Heading::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn turn_around(self) -> Heading {
+        Heading::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    // One step in this heading when rows grow down the page, so north is (-1, 0)
+    pub fn delta<T: PrimInt + Signed>(self) -> Coordinate<T> {
+        self.delta_in(Origin::TopLeft)
+    }
+
+    // One step in this heading, with north going up whichever way rows grow
+    pub fn delta_in<T: PrimInt + Signed>(self, origin: Origin) -> Coordinate<T> {
+        let (one, zero) = (T::one(), T::zero());
+        let up = match origin {
+            Origin::TopLeft => -one,
+            Origin::BottomLeft => one,
+        };
+        let (row, col) = match self {
+            Heading::North => (up, zero),
+            Heading::NorthEast => (up, one),
+            Heading::East => (zero, one),
+            Heading::SouthEast => (-up, one),
+            Heading::South => (-up, zero),
+            Heading::SouthWest => (-up, -one),
+            Heading::West => (zero, -one),
+            Heading::NorthWest => (up, -one),
+        };
+        Coordinate { row, col }
+    }
+}
+
+impl From<Direction> for Heading {
+    fn from(direction: Direction) -> Self {
+        Heading::ALL[direction as usize * 2]
+    }
+}
+
+impl TryFrom<Heading> for Direction {
+    type Error = color_eyre::Report;
+
+    fn try_from(heading: Heading) -> Result<Self, Self::Error> {
+        if !heading.is_cardinal() {
+            bail!("{} isn't a cardinal direction", heading);
+        }
+        Ok(Direction::ALL[heading as usize / 2])
+    }
+}
+
+const HEADING_NAMES: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+// Accepts N, NE, E... as well as anything a Direction can be parsed from
+impl FromStr for Heading {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match HEADING_NAMES.iter().position(|&name| name == s) {
+            Some(index) => Ok(Heading::ALL[index]),
+            None => s.parse::<Direction>().map(Heading::from),
+        }
+    }
+}
+
+impl Display for Heading {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HEADING_NAMES[*self as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.turn_around(), Direction::West);
+        assert_eq!(Heading::North.turn_left(), Heading::NorthWest);
+        assert_eq!(Heading::NorthWest.turn_right(), Heading::North);
+        assert_eq!(Heading::SouthWest.turn_around(), Heading::NorthEast);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.turn_around());
+        }
+    }
+
+    #[test]
+    fn test_deltas() {
+        assert_eq!(Direction::North.delta::<i32>(), Coordinate::new(-1, 0));
+        assert_eq!(
+            Direction::North.delta_in::<i32>(Origin::BottomLeft),
+            Coordinate::new(1, 0)
+        );
+        assert_eq!(Heading::SouthEast.delta::<i64>(), Coordinate::new(1, 1));
+        assert_eq!(
+            Heading::SouthEast.delta_in::<i64>(Origin::BottomLeft),
+            Coordinate::new(-1, 1)
+        );
+        // turning matches rotating the delta
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.turn_right().delta::<i32>(),
+                direction.delta::<i32>().rotate_clockwise()
+            );
+        }
+        let total = Heading::ALL
+            .iter()
+            .fold(Coordinate::new(0, 0), |sum, h| sum + h.delta::<i32>());
+        assert_eq!(total, Coordinate::new(0, 0));
+    }
+
+    #[test]
+    fn test_parse() -> color_eyre::Result<()> {
+        let parsed = "URDL"
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(parsed, Direction::ALL);
+        assert_eq!("N".parse::<Direction>()?, Direction::North);
+        assert_eq!(Direction::try_from('<')?, Direction::West);
+        assert_eq!(Direction::try_from('v')?, Direction::South);
+        assert!("X".parse::<Direction>().is_err());
+        assert!("NE".parse::<Direction>().is_err());
+        assert_eq!("NE".parse::<Heading>()?, Heading::NorthEast);
+        assert_eq!(">".parse::<Heading>()?, Heading::East);
+        assert_eq!(Direction::try_from(Heading::South)?, Direction::South);
+        assert!(Direction::try_from(Heading::SouthWest).is_err());
+        assert_eq!(Heading::SouthWest.to_string(), "SW");
+        Ok(())
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use color_eyre::eyre::bail;
+
+use crate::{
+    get_adjacent_points, get_adjacent_points_diagonal, parsers::ParseError, IndexingCoordinate,
+};
+
+// A rectangular grid stored row by row, indexed with row 0 at the top
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> color_eyre::Result<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((index, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            bail!(
+                "Row {} has {} cells, expected {} like the first row",
+                index + 1,
+                row.len(),
+                width
+            );
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Each line of the input becomes a row, with every char mapped to a cell.
+    // expected describes what f accepts, for the error when it doesn't.
+    pub fn parse<F>(input: &str, expected: &str, mut f: F) -> color_eyre::Result<Grid<T>>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut rows: Vec<Vec<T>> = vec![];
+        for (index, line) in input.lines().enumerate() {
+            let row = line
+                .char_indices()
+                .map(|(offset, c)| {
+                    f(c).ok_or_else(|| ParseError {
+                        line: index + 1,
+                        ..ParseError::at(line, &line[offset..], expected)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    let width = first.len();
+                    let offset = line
+                        .char_indices()
+                        .nth(width)
+                        .map_or(line.len(), |(i, _)| i);
+                    return Err(ParseError {
+                        line: index + 1,
+                        ..ParseError::at(
+                            line,
+                            &line[offset..],
+                            format!("{} cells like the first row", width),
+                        )
+                    }
+                    .into());
+                }
+            }
+            rows.push(row);
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coordinate: IndexingCoordinate) -> bool {
+        coordinate.row < self.height && coordinate.col < self.width
+    }
+
+    pub fn get(&self, coordinate: IndexingCoordinate) -> Option<&T> {
+        if self.contains(coordinate) {
+            Some(&self.cells[coordinate.row * self.width + coordinate.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coordinate: IndexingCoordinate) -> Option<&mut T> {
+        if self.contains(coordinate) {
+            Some(&mut self.cells[coordinate.row * self.width + coordinate.col])
+        } else {
+            None
+        }
+    }
+
+    // Every coordinate, row by row
+    pub fn coordinates(&self) -> impl Iterator<Item = IndexingCoordinate> {
+        let width = self.width;
+        (0..self.height * width).map(move |i| IndexingCoordinate::new(i / width, i % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (IndexingCoordinate, &T)> {
+        self.coordinates().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {} is outside the grid", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    // Up, down, left and right, skipping anything off the edge
+    pub fn neighbours(&self, coordinate: IndexingCoordinate) -> Vec<IndexingCoordinate> {
+        get_adjacent_points(coordinate, self.height, self.width)
+    }
+
+    // As neighbours, plus the four diagonals
+    pub fn neighbours_diagonal(&self, coordinate: IndexingCoordinate) -> Vec<IndexingCoordinate> {
+        get_adjacent_points_diagonal(coordinate, self.height, self.width)
+    }
+
+    pub fn position<F>(&self, mut predicate: F) -> Option<IndexingCoordinate>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(coordinate, _)| coordinate)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn rearrange<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(IndexingCoordinate) -> IndexingCoordinate,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[source(IndexingCoordinate::new(i / width, i % width))].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Rows become columns
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |c| c.transpose())
+    }
+
+    // The left column becomes the top row
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(self.height, self.width, |c| {
+            IndexingCoordinate::new(height - 1 - c.col, c.row)
+        })
+    }
+
+    // The top row becomes the left column
+    pub fn rotate_anticlockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.height, self.width, |c| {
+            IndexingCoordinate::new(c.col, width - 1 - c.row)
+        })
+    }
+}
+
+// Panics outside the grid, use get for a checked lookup
+impl<T> Index<IndexingCoordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: IndexingCoordinate) -> &Self::Output {
+        match self.get(coordinate) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                coordinate, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<IndexingCoordinate> for Grid<T> {
+    fn index_mut(&mut self, coordinate: IndexingCoordinate) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(coordinate) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", coordinate, width, height),
+        }
+    }
+}
+
+// One line per row with the cells written back to back
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = digits("123\n456");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[IndexingCoordinate::new(1, 0)], 4);
+        assert_eq!(grid.get(IndexingCoordinate::new(0, 2)), Some(&3));
+        assert_eq!(grid.get(IndexingCoordinate::new(2, 0)), None);
+        assert_eq!(grid.get(IndexingCoordinate::new(0, 3)), None);
+        assert_eq!(
+            grid.position(|&c| c == 5),
+            Some(IndexingCoordinate::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("12\n3x4", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 2: expected a digit but found \"x4\""
+        );
+        let error = Grid::parse("12\n3", "a cell", Some).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 2: expected 2 cells like the first row but found the end of the line"
+        );
+        let error = Grid::parse("12\n345", "a cell", Some).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 3: expected 2 cells like the first row but found \"5\""
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let corner = IndexingCoordinate::new(0, 0);
+        assert_eq!(grid.neighbours(corner).len(), 2);
+        assert_eq!(grid.neighbours_diagonal(corner).len(), 3);
+        let centre = IndexingCoordinate::new(1, 1);
+        let mut around = grid
+            .neighbours_diagonal(centre)
+            .into_iter()
+            .map(|c| grid[c])
+            .collect::<Vec<_>>();
+        around.sort();
+        assert_eq!(around, vec![1, 2, 3, 4, 6, 7, 8, 9]);
+        let empty = Grid::<u32>::from_rows(vec![]).unwrap();
+        assert!(empty.neighbours(corner).is_empty());
+        assert!(empty.neighbours_diagonal(corner).is_empty());
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        let mut grid = grid;
+        grid[IndexingCoordinate::new(0, 0)] = 0;
+        assert_eq!(grid.to_string(), "023\n456");
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::eyre::{eyre, WrapErr};
+
+// Conventional inputs live in input/ under the current directory, override with AOC_INPUT_DIR
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    // input/dayN.txt
+    Real,
+    // input/dayN.test.txt
+    Test,
+    // input/dayN.test.2.txt
+    Test2,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // Whether the input is one of the worked examples, which some days solve with
+    // different parameters. Explicit paths count as examples if named like one.
+    pub fn is_example(&self) -> bool {
+        match self {
+            InputSource::Real | InputSource::Stdin => false,
+            InputSource::Test | InputSource::Test2 => true,
+            InputSource::Path(path) => path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().contains(".test")),
+        }
+    }
+
+    // Only conventional inputs depend on the day
+    pub fn is_per_day(&self) -> bool {
+        matches!(
+            self,
+            InputSource::Real | InputSource::Test | InputSource::Test2
+        )
+    }
+
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        let file_name = match self {
+            InputSource::Real => format!("day{}.txt", day),
+            InputSource::Test => format!("day{}.test.txt", day),
+            InputSource::Test2 => format!("day{}.test.2.txt", day),
+            InputSource::Path(path) => return Some(path.clone()),
+            InputSource::Stdin => return None,
+        };
+        Some(input_dir().join(file_name))
+    }
+
+    // How to refer to the input in messages
+    pub fn name(&self, day: u32) -> String {
+        match self.path(day) {
+            Some(path) => path.display().to_string(),
+            None => "stdin".to_owned(),
+        }
+    }
+
+    // The input to read as it goes, for days that don't need it all at once
+    pub fn open(&self, day: u32) -> color_eyre::Result<Box<dyn Read>> {
+        match self.path(day) {
+            Some(path) => Ok(Box::new(
+                fs::File::open(&path).map_err(|e| read_error(&path, e))?,
+            )),
+            None => Ok(Box::new(io::stdin())),
+        }
+    }
+
+    pub fn read(&self, day: u32) -> color_eyre::Result<String> {
+        match self.path(day) {
+            Some(path) => read_file(&path),
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .wrap_err("Failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "real" => Ok(InputSource::Real),
+            "test" => Ok(InputSource::Test),
+            "test2" => Ok(InputSource::Test2),
+            "-" => Ok(InputSource::Stdin),
+            "" => Err(eyre!(
+                "Empty input, expected real, test, test2, - or a path"
+            )),
+            path => Ok(InputSource::Path(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Real => write!(f, "real"),
+            InputSource::Test => write!(f, "test"),
+            InputSource::Test2 => write!(f, "test2"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+fn input_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from("input"),
+    }
+}
+
+pub fn read_file(path: &Path) -> color_eyre::Result<String> {
+    fs::read_to_string(path).map_err(|e| read_error(path, e))
+}
+
+fn read_error(path: &Path, error: io::Error) -> color_eyre::Report {
+    match error.kind() {
+        io::ErrorKind::NotFound => eyre!("Missing input file {}", path.display()),
+        _ => eyre!("Failed to read {}: {}", path.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventional_paths() {
+        let real = InputSource::Real.path(3).unwrap();
+        assert!(real.ends_with("input/day3.txt"));
+        let test2 = InputSource::Test2.path(9).unwrap();
+        assert!(test2.ends_with("input/day9.test.2.txt"));
+        assert_eq!(InputSource::Stdin.path(1), None);
+        assert_eq!(InputSource::Stdin.name(1), "stdin");
+    }
+
+    #[test]
+    fn test_parse_source() -> color_eyre::Result<()> {
+        assert_eq!("test".parse::<InputSource>()?, InputSource::Test);
+        assert_eq!("-".parse::<InputSource>()?, InputSource::Stdin);
+        let path = "other/day15.test.txt".parse::<InputSource>()?;
+        assert_eq!(
+            path,
+            InputSource::Path(PathBuf::from("other/day15.test.txt"))
+        );
+        assert!(path.is_example());
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_file_is_named() {
+        let error = InputSource::Path(PathBuf::from("input/day99.txt"))
+            .read(99)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Missing input file input/day99.txt");
+        let error = InputSource::Path(PathBuf::from("input/day99.txt"))
+            .open(99)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Missing input file input/day99.txt");
+    }
+}
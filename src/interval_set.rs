@@ -0,0 +1,267 @@
+use std::ops::RangeInclusive;
+
+use num::PrimInt;
+
+use crate::Range;
+
+// A set of integers stored as sorted, disjoint inclusive ranges. Ranges that
+// overlap or sit next to each other are merged, so 1..=3 and 4..=5 become 1..=5.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T: PrimInt> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Number of values covered. A set covering every value of T has one more
+    // than T can hold, so this is a u128, which only overflows for all of a
+    // 128-bit type.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(lower, upper)| {
+                // both ends fit in an i128 unless T is u128, where the
+                // difference can't overflow
+                let width = match (lower.to_i128(), upper.to_i128()) {
+                    (Some(lower), Some(upper)) => upper.abs_diff(lower),
+                    _ => (upper - lower).to_u128().unwrap(),
+                };
+                width + 1
+            })
+            .sum()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(lower, upper)| lower..=upper)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lower, mut upper) = range.into_inner();
+        if lower > upper {
+            return;
+        }
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut placed = false;
+        for &(start, end) in &self.ranges {
+            // comparing before adding one keeps clear of overflow at T::max_value
+            if end < lower && end + T::one() != lower {
+                ranges.push((start, end));
+            } else if start > upper && start - T::one() != upper {
+                if !placed {
+                    ranges.push((lower, upper));
+                    placed = true;
+                }
+                ranges.push((start, end));
+            } else {
+                lower = lower.min(start);
+                upper = upper.max(end);
+            }
+        }
+        if !placed {
+            ranges.push((lower, upper));
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (lower, upper) = range.into_inner();
+        if lower > upper {
+            return;
+        }
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        for &(start, end) in &self.ranges {
+            if end < lower || start > upper {
+                ranges.push((start, end));
+                continue;
+            }
+            if start < lower {
+                ranges.push((start, lower - T::one()));
+            }
+            if end > upper {
+                ranges.push((upper + T::one(), end));
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    // True if every value in the range is in the set
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (lower, upper) = range.into_inner();
+        lower > upper
+            || self
+                .find(lower)
+                .is_some_and(|index| self.ranges[index].1 >= upper)
+    }
+
+    fn find(&self, value: T) -> Option<usize> {
+        let index = self.ranges.partition_point(|&(_, end)| end < value);
+        match self.ranges.get(index) {
+            Some(&(start, _)) if start <= value => Some(index),
+            _ => None,
+        }
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        result.extend(other.ranges());
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(&&(a_start, a_end)), Some(&&(b_start, b_end))) = (a.peek(), b.peek()) {
+            let (lower, upper) = (a_start.max(b_start), a_end.min(b_end));
+            if lower <= upper {
+                ranges.push((lower, upper));
+            }
+            // whichever finishes first can't overlap anything else
+            if a_end < b_end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for range in other.ranges() {
+            result.remove(range);
+        }
+        result
+    }
+
+    // The ranges within bounds which aren't in the set
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> impl Iterator<Item = RangeInclusive<T>> {
+        IntervalSet::from_iter([bounds])
+            .difference(self)
+            .ranges
+            .into_iter()
+            .map(|(lower, upper)| lower..=upper)
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl From<Range> for RangeInclusive<i32> {
+    fn from(range: Range) -> Self {
+        range.lower..=range.upper
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut intervals = set(&[5..=7, 1..=2, 10..=12]);
+        assert_eq!(ranges(&intervals), vec![1..=2, 5..=7, 10..=12]);
+        // adjacent ranges join up
+        intervals.insert(3..=4);
+        assert_eq!(ranges(&intervals), vec![1..=7, 10..=12]);
+        intervals.insert(6..=11);
+        assert_eq!(ranges(&intervals), vec![1..=12]);
+        // empty ranges are ignored
+        intervals.insert(RangeInclusive::new(20, 19));
+        assert_eq!(intervals.len(), 12);
+        // no overflow at the edges of the type
+        let mut edges: IntervalSet<u8> = [250..=255, 0..=3].into_iter().collect();
+        edges.insert(4..=249);
+        assert_eq!(edges.ranges().collect::<Vec<_>>(), vec![0..=255]);
+    }
+
+    #[test]
+    fn test_len_of_whole_type() {
+        let bytes: IntervalSet<u8> = [0..=255].into_iter().collect();
+        assert_eq!(bytes.len(), 256);
+        let all: IntervalSet<i32> = [i32::MIN..=-1, 0..=i32::MAX].into_iter().collect();
+        assert_eq!(all.len(), 1 << 32);
+        let all: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(all.len(), 1 << 64);
+        let top: IntervalSet<u128> = [u128::MAX - 9..=u128::MAX].into_iter().collect();
+        assert_eq!(top.len(), 10);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut intervals = set(&[1..=10, 20..=30]);
+        intervals.remove(5..=22);
+        assert_eq!(ranges(&intervals), vec![1..=4, 23..=30]);
+        intervals.remove(1..=1);
+        intervals.remove(30..=40);
+        assert_eq!(ranges(&intervals), vec![2..=4, 23..=29]);
+        assert_eq!(intervals.len(), 3 + 7);
+    }
+
+    #[test]
+    fn test_contains() {
+        let intervals = set(&[1..=3, 7..=9]);
+        assert!(intervals.contains(1));
+        assert!(intervals.contains(9));
+        assert!(!intervals.contains(5));
+        assert!(!intervals.contains(0));
+        assert!(intervals.contains_range(7..=8));
+        assert!(!intervals.contains_range(2..=7));
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=5, 10..=15]);
+        let b = set(&[4..=11, 20..=21]);
+        assert_eq!(ranges(&a.union(&b)), vec![1..=15, 20..=21]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![4..=5, 10..=11]);
+        assert_eq!(ranges(&a.difference(&b)), vec![1..=3, 12..=15]);
+        assert_eq!(ranges(&b.difference(&a)), vec![6..=9, 20..=21]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let intervals = set(&[-2..=3, 6..=8]);
+        assert_eq!(
+            intervals.gaps(0..=10).collect::<Vec<_>>(),
+            vec![4..=5, 9..=10]
+        );
+        assert_eq!(intervals.gaps(0..=3).count(), 0);
+    }
+}
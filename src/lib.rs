@@ -1,13 +1,235 @@
 use std::cmp::{max, min};
-use std::fs::File;
-use std::io::BufReader;
-use std::io::{BufRead, Lines};
-use std::ops::Add;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+use color_eyre::eyre::{bail, eyre, WrapErr};
 use itertools::Itertools;
-use num::{range_inclusive, PrimInt};
+use num::{range_inclusive, PrimInt, Signed};
 use reformation::Reformation;
 
+pub mod answers;
+pub mod bench;
+pub mod coordinate3;
+pub mod cycle;
+pub mod days;
+pub mod direction;
+pub mod grid;
+pub mod input;
+pub mod interval_set;
+pub mod memo;
+pub mod ocr;
+pub mod parsers;
+pub mod render;
+pub mod search;
+pub mod simulation;
+pub mod sparse_grid;
+
+pub use coordinate3::{Bounds3, Coordinate3};
+pub use direction::{Direction, Heading};
+pub use grid::Grid;
+pub use interval_set::IntervalSet;
+pub use memo::{Memo, MemoStats};
+pub use parsers::ParseError;
+pub use sparse_grid::{Bounds, Origin, SparseGrid};
+
+use simulation::Recorder;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(eyre!("Invalid part {}, expected 1 or 2", s)),
+        }
+    }
+}
+
+// Every day implements this, see src/template.rs for a starting point
+pub trait Solution {
+    const DAY: u32;
+    // Day 25 only has one puzzle
+    const HAS_PART2: bool = true;
+
+    // The parsed puzzle input, which may borrow from the raw text
+    type Input<'a>;
+    // Anything that differs between the worked example and the real puzzle,
+    // e.g. which row to scan. Default gives the values for the real input.
+    type Params: Default;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn example_params() -> Self::Params {
+        Self::Params::default()
+    }
+
+    // Change one of the Params by name, for `aoc <day> --param name=value`
+    fn set_param(_params: &mut Self::Params, name: &str, _value: &str) -> color_eyre::Result<()> {
+        bail!("Day {} has no parameter {}", Self::DAY, name)
+    }
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>>;
+
+    fn solve_part1(
+        input: &Self::Input<'_>,
+        params: &Self::Params,
+    ) -> color_eyre::Result<Self::Part1>;
+
+    fn solve_part2(
+        _input: &Self::Input<'_>,
+        _params: &Self::Params,
+    ) -> color_eyre::Result<Self::Part2> {
+        bail!("Day {} has no part 2", Self::DAY)
+    }
+
+    // Days that simulate step by step play it out for the recorder here, see
+    // simulation::Simulation. None when the part has nothing to show, and an
+    // error if the simulation couldn't carry on.
+    fn record(
+        _input: &Self::Input<'_>,
+        _params: &Self::Params,
+        _part: Part,
+        _recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+// Object safe view of a Solution, so every day can live in one list
+pub trait Puzzle: Sync {
+    fn day(&self) -> u32;
+
+    fn has_part(&self, part: Part) -> bool;
+
+    // Parse the input and solve one part, timing each step. `example` picks the
+    // parameters for the worked example instead of the real puzzle.
+    fn run(&self, part: Part, input: &str, example: bool) -> color_eyre::Result<Timed> {
+        self.run_with(part, input, example, &[])
+    }
+
+    // As run, with some parameters changed by name, see Solution::set_param
+    fn run_with(
+        &self,
+        part: Part,
+        input: &str,
+        example: bool,
+        params: &[(String, String)],
+    ) -> color_eyre::Result<Timed>;
+
+    fn solve(&self, part: Part, input: &str, example: bool) -> color_eyre::Result<String> {
+        Ok(self.run(part, input, example)?.answer)
+    }
+
+    // Parse the input and record the simulation for one part, if there is one
+    fn record(
+        &self,
+        part: Part,
+        input: &str,
+        example: bool,
+        params: &[(String, String)],
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct Timed {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn has_part(&self, part: Part) -> bool {
+        part == Part::One || S::HAS_PART2
+    }
+
+    fn run_with(
+        &self,
+        part: Part,
+        input: &str,
+        example: bool,
+        params: &[(String, String)],
+    ) -> color_eyre::Result<Timed> {
+        let params = params_for::<S>(example, params)?;
+        let time = Instant::now();
+        let input = S::parse(input)?;
+        let parse = time.elapsed();
+        let time = Instant::now();
+        let answer = match part {
+            Part::One => S::solve_part1(&input, &params)?.to_string(),
+            Part::Two => S::solve_part2(&input, &params)?.to_string(),
+        };
+        Ok(Timed {
+            answer,
+            parse,
+            solve: time.elapsed(),
+        })
+    }
+
+    fn record(
+        &self,
+        part: Part,
+        input: &str,
+        example: bool,
+        params: &[(String, String)],
+        recorder: &Recorder,
+    ) -> color_eyre::Result<Option<String>> {
+        let params = params_for::<S>(example, params)?;
+        let input = S::parse(input)?;
+        S::record(&input, &params, part, recorder)
+    }
+}
+
+// The example's or the real puzzle's parameters, with any named ones changed
+fn params_for<S: Solution>(
+    example: bool,
+    changes: &[(String, String)],
+) -> color_eyre::Result<S::Params> {
+    let mut params = if example {
+        S::example_params()
+    } else {
+        S::Params::default()
+    };
+    for (name, value) in changes {
+        S::set_param(&mut params, name, value)?;
+    }
+    Ok(params)
+}
+
+// A parameter's value, for Solution::set_param
+pub fn parse_param<T: FromStr>(name: &str, value: &str) -> color_eyre::Result<T> {
+    value
+        .parse()
+        .map_err(|_| eyre!("Invalid value {} for parameter {}", value, name))
+}
+
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
 pub struct Coordinate<T: PrimInt> {
     pub row: T,
@@ -24,20 +246,20 @@ impl Coordinate<usize> {
     }
 }
 
-impl Into<Coordinate<usize>> for (usize, usize) {
-    fn into(self) -> Coordinate<usize> {
+impl From<(usize, usize)> for Coordinate<usize> {
+    fn from(pair: (usize, usize)) -> Self {
         Coordinate {
-            row: self.0,
-            col: self.1,
+            row: pair.0,
+            col: pair.1,
         }
     }
 }
 
-impl Into<Coordinate<i32>> for (i32, i32) {
-    fn into(self) -> Coordinate<i32> {
+impl From<(i32, i32)> for Coordinate<i32> {
+    fn from(pair: (i32, i32)) -> Self {
         Coordinate {
-            row: self.0,
-            col: self.1,
+            row: pair.0,
+            col: pair.1,
         }
     }
 }
@@ -77,6 +299,71 @@ impl<T: PrimInt> Coordinate<T> {
             col: self.row,
         }
     }
+
+    // Steps between the two moving only along rows and columns
+    pub fn manhattan(&self, other: &Coordinate<T>) -> T {
+        abs_diff(self.row, other.row) + abs_diff(self.col, other.col)
+    }
+
+    // Steps between the two when diagonal moves are allowed
+    pub fn chebyshev(&self, other: &Coordinate<T>) -> T {
+        max(abs_diff(self.row, other.row), abs_diff(self.col, other.col))
+    }
+
+    // Add a signed offset, None if the result doesn't fit in T, e.g. going
+    // above row 0 of an IndexingCoordinate
+    pub fn checked_offset(&self, delta: Coordinate<i64>) -> Option<Coordinate<T>> {
+        let offset = self.cast::<i64>()?;
+        Coordinate {
+            row: offset.row.checked_add(delta.row)?,
+            col: offset.col.checked_add(delta.col)?,
+        }
+        .cast()
+    }
+
+    // Convert between IndexingCoordinate, PosCoordinate and the signed
+    // variants, None if either value is out of range for U
+    pub fn cast<U: PrimInt>(&self) -> Option<Coordinate<U>> {
+        Some(Coordinate {
+            row: U::from(self.row)?,
+            col: U::from(self.col)?,
+        })
+    }
+}
+
+fn abs_diff<T: PrimInt>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+// Rotations assume rows increase downwards, so up is (-1, 0)
+impl<T: PrimInt + Signed> Coordinate<T> {
+    // -1, 0 or 1 on each axis, the step towards this coordinate from the origin
+    pub fn signum(&self) -> Coordinate<T> {
+        Coordinate {
+            row: self.row.signum(),
+            col: self.col.signum(),
+        }
+    }
+
+    // Quarter turn about the origin, so up becomes right
+    pub fn rotate_clockwise(&self) -> Coordinate<T> {
+        Coordinate {
+            row: self.col,
+            col: -self.row,
+        }
+    }
+
+    // Quarter turn about the origin, so up becomes left
+    pub fn rotate_anticlockwise(&self) -> Coordinate<T> {
+        Coordinate {
+            row: -self.col,
+            col: self.row,
+        }
+    }
 }
 
 impl<T: PrimInt> Add for Coordinate<T> {
@@ -90,6 +377,51 @@ impl<T: PrimInt> Add for Coordinate<T> {
     }
 }
 
+impl<T: PrimInt> AddAssign for Coordinate<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: PrimInt> Sub for Coordinate<T> {
+    type Output = Coordinate<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coordinate {
+            row: self.row - rhs.row,
+            col: self.col - rhs.col,
+        }
+    }
+}
+
+impl<T: PrimInt> SubAssign for Coordinate<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Coordinate<T> {
+    type Output = Coordinate<T>;
+
+    fn neg(self) -> Self::Output {
+        Coordinate {
+            row: -self.row,
+            col: -self.col,
+        }
+    }
+}
+
+impl<T: PrimInt> Mul<T> for Coordinate<T> {
+    type Output = Coordinate<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Coordinate {
+            row: self.row * rhs,
+            col: self.col * rhs,
+        }
+    }
+}
+
 #[derive(Reformation, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 #[reformation("{lower}-{upper}")]
 pub struct Range {
@@ -118,7 +450,7 @@ impl Range {
             || other.upper == self.lower - 1
     }
 
-    // assume overlap
+    // assume overlap, see IntervalSet for merging any number of ranges
     pub fn merge(&self, other: &Range) -> Range {
         Range {
             lower: min(self.lower, other.lower),
@@ -151,48 +483,66 @@ struct Date {
     minute: u8,
 }
 
-pub fn group_file_by_empty_lines(reader: BufReader<File>) -> Vec<Vec<String>> {
+// The helpers below take any BufRead, e.g. a BufReader<File>, stdin().lock()
+// or input.as_bytes() for a string
+
+pub fn group_file_by_empty_lines<R: BufRead>(reader: R) -> color_eyre::Result<Vec<Vec<String>>> {
     reader
         .lines()
-        .map(|line| line.unwrap())
-        .fold(vec![vec![]], |mut result, line| {
+        .try_fold(vec![vec![]], |mut result: Vec<Vec<String>>, line| {
+            let line = line.wrap_err("Failed to read input")?;
             if line.trim().is_empty() {
                 result.push(Vec::new());
-                result
             } else {
                 result.last_mut().unwrap().push(line);
-                result
             }
+            Ok(result)
         })
 }
 
-// Create a method for parsing a line of ints
-pub fn parse_line_to_num(line: &str) -> Vec<i32> {
+// Parse a line of whitespace separated values
+pub fn parse_line_to_num<T: FromStr>(line: &str) -> color_eyre::Result<Vec<T>> {
     line.split_whitespace()
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect_vec()
+        .map(|token| {
+            token.parse::<T>().map_err(|_| {
+                let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+                ParseError::at(line, &line[offset..], short_type_name::<T>()).into()
+            })
+        })
+        .collect()
 }
 
-// Create a method for parsing lines of a file to ints
-pub fn parse_lines_to_nums(lines: Lines<BufReader<File>>) -> Vec<i32> {
-    lines
-        .map(|line| line.unwrap().parse::<i32>().unwrap())
-        .collect_vec()
+// Parse every line as one value
+pub fn parse_lines_to_nums<T: FromStr, R: BufRead>(reader: R) -> color_eyre::Result<Vec<T>> {
+    let input = read_all(reader)?;
+    parsers::each_line(&input, short_type_name::<T>(), str::parse)
 }
 
-// #[allow(dead_code)]
-// fn parse_dates(reader: BufReader<File>) -> Vec<Date> {
-//     parse_lines_to_struct::<Date>(reader)
-// }
+// Parse every line as one value using reformation. The lines don't outlive
+// this, so T can't borrow from them.
+pub fn parse_lines_to_struct<T, R>(reader: R) -> color_eyre::Result<Vec<T>>
+where
+    T: for<'a> Reformation<'a>,
+    R: BufRead,
+{
+    let input = read_all(reader)?;
+    let expected = format!("a line matching {:?}", T::regex_str());
+    parsers::each_line(&input, &expected, T::parse)
+}
 
-// // Create a method for parsing lines of a file to a particular struct using reformation
-// #[allow(dead_code)]
-// pub fn parse_lines_to_struct<'a, T: Reformation<'a>>(reader: BufReader<File>) -> Vec<T> {
-//     reader
-//         .lines()
-//         .map(|line| T::parse(&line.unwrap()).unwrap())
-//         .collect_vec()
-// }
+fn read_all<R: BufRead>(mut reader: R) -> color_eyre::Result<String> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .wrap_err("Failed to read input")?;
+    Ok(input)
+}
+
+// i32 rather than core::i32, for error messages
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
 
 pub fn get_adjacent_points<T: PrimInt>(
     coordinate: Coordinate<T>,
@@ -203,13 +553,20 @@ pub fn get_adjacent_points<T: PrimInt>(
     let min_row = T::min_value();
     let min_col = T::min_value();
     let one = T::one();
+    // there's nothing further on in an empty grid
+    let has_next_row = max_row
+        .checked_sub(&one)
+        .is_some_and(|last| coordinate.row < last);
+    let has_next_col = max_col
+        .checked_sub(&one)
+        .is_some_and(|last| coordinate.col < last);
     if coordinate.row != min_row {
         adj.push(Coordinate {
             row: coordinate.row - one,
             col: coordinate.col,
         });
     }
-    if coordinate.row != max_row - one {
+    if has_next_row {
         adj.push(Coordinate {
             row: coordinate.row + one,
             col: coordinate.col,
@@ -221,7 +578,7 @@ pub fn get_adjacent_points<T: PrimInt>(
             col: coordinate.col - one,
         });
     }
-    if coordinate.col != max_col - one {
+    if has_next_col {
         adj.push(Coordinate {
             row: coordinate.row,
             col: coordinate.col + one,
@@ -239,25 +596,31 @@ pub fn get_adjacent_points_diagonal<T: PrimInt>(
     let min_row = T::min_value();
     let min_col = T::min_value();
     let one = T::one();
+    let has_next_row = max_row
+        .checked_sub(&one)
+        .is_some_and(|last| coordinate.row < last);
+    let has_next_col = max_col
+        .checked_sub(&one)
+        .is_some_and(|last| coordinate.col < last);
     if coordinate.row != min_row && coordinate.col != min_col {
         adj.push(Coordinate {
             row: coordinate.row - one,
             col: coordinate.col - one,
         });
     }
-    if coordinate.row != max_row - one && coordinate.col != max_col - one {
+    if has_next_row && has_next_col {
         adj.push(Coordinate {
             row: coordinate.row + one,
             col: coordinate.col + one,
         });
     }
-    if coordinate.col != min_col && coordinate.row != max_row - one {
+    if coordinate.col != min_col && has_next_row {
         adj.push(Coordinate {
             row: coordinate.row + one,
             col: coordinate.col - one,
         });
     }
-    if coordinate.col != max_col - one && coordinate.row != min_row {
+    if has_next_col && coordinate.row != min_row {
         adj.push(Coordinate {
             row: coordinate.row - one,
             col: coordinate.col + one,
@@ -266,37 +629,104 @@ pub fn get_adjacent_points_diagonal<T: PrimInt>(
     adj
 }
 
-pub fn print_matrix(matrix: &[Vec<u32>]) {
-    for line in matrix {
-        println!("{}", line.iter().join(""));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinate_arithmetic() {
+        let a = Coordinate::new(2, -3);
+        let b = Coordinate::new(-1, 5);
+        assert_eq!(a + b, Coordinate::new(1, 2));
+        assert_eq!(a - b, Coordinate::new(3, -8));
+        assert_eq!(-a, Coordinate::new(-2, 3));
+        assert_eq!(a * 3, Coordinate::new(6, -9));
+        let mut c = a;
+        c += b;
+        c -= Coordinate::new(1, 1);
+        assert_eq!(c, Coordinate::new(0, 1));
     }
-    println!();
-}
 
-pub fn print_coordinates<T: PrimInt>(matrix: &[Coordinate<T>], origin_top_left: bool) {
-    let min_row = matrix.iter().map(|c| c.row).min().unwrap();
-    let max_row = matrix.iter().map(|c| c.row).max().unwrap();
-    let min_col = matrix.iter().map(|c| c.col).min().unwrap();
-    let max_col = matrix.iter().map(|c| c.col).max().unwrap();
-    let row_iter = if origin_top_left {
-        range_inclusive(min_row, max_row).collect_vec()
-    } else {
-        range_inclusive(min_row, max_row)
-            .collect_vec()
-            .into_iter()
-            .rev()
-            .collect_vec()
-    };
-    for row in row_iter {
-        for col in range_inclusive(min_col, max_col) {
-            let c = Coordinate { row, col };
-            if matrix.contains(&c) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+    #[test]
+    fn test_distances() {
+        let a = Coordinate::new(2, -3);
+        let b = Coordinate::new(-1, 5);
+        assert_eq!(a.manhattan(&b), 11);
+        assert_eq!(a.chebyshev(&b), 8);
+        // unsigned coordinates mustn't underflow
+        let c: IndexingCoordinate = Coordinate::new(1, 7);
+        let d: IndexingCoordinate = Coordinate::new(4, 2);
+        assert_eq!(c.manhattan(&d), 8);
+        assert_eq!(d.chebyshev(&c), 5);
+    }
+
+    #[test]
+    fn test_signum_and_rotation() {
+        assert_eq!(Coordinate::new(-4, 0).signum(), Coordinate::new(-1, 0));
+        assert_eq!(Coordinate::new(7, -2).signum(), Coordinate::new(1, -1));
+        let up = Coordinate::new(-1, 0);
+        let right = Coordinate::new(0, 1);
+        assert_eq!(up.rotate_clockwise(), right);
+        assert_eq!(right.rotate_anticlockwise(), up);
+        assert_eq!(
+            Coordinate::new(2, 3)
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            Coordinate::new(2, 3)
+        );
+    }
+
+    #[test]
+    fn test_checked_offset_and_cast() {
+        let origin: IndexingCoordinate = Coordinate::new(0, 3);
+        assert_eq!(origin.checked_offset(Coordinate::new(-1, 0)), None);
+        assert_eq!(
+            origin.checked_offset(Coordinate::new(2, -3)),
+            Some(Coordinate::new(2, 0))
+        );
+        let pos: PosCoordinate = Coordinate::new(5, 6);
+        assert_eq!(pos.cast::<usize>(), Some(Coordinate::new(5, 6)));
+        assert_eq!(pos.cast::<i32>(), Some(Coordinate::new(5, 6)));
+        assert_eq!(Coordinate::new(-1, 2).cast::<usize>(), None);
+        assert_eq!(Coordinate::new(300, 2).cast::<u8>(), None);
+    }
+
+    #[test]
+    fn test_input_helpers() -> color_eyre::Result<()> {
+        let groups = group_file_by_empty_lines("1\n2\n\n3".as_bytes())?;
+        assert_eq!(groups, vec![vec!["1", "2"], vec!["3"]]);
+        assert_eq!(parse_line_to_num::<u64>(" 4  17 9")?, vec![4, 17, 9]);
+        let error = parse_line_to_num::<u8>("4 17 300").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 1, column 6: expected u8 but found \"300\""
+        );
+        let values: Vec<f64> = parse_lines_to_nums("1.5\n-2".as_bytes())?;
+        assert_eq!(values, vec![1.5, -2.0]);
+        let error = parse_lines_to_nums::<i32, _>("1\n2\nthree\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 3, column 1: expected i32 but found \"three\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_lines_to_struct() -> color_eyre::Result<()> {
+        let ants: Vec<Ant> = parse_lines_to_struct("Queen(Sarah)\nWorker(3)\nWarrior".as_bytes())?;
+        assert_eq!(
+            ants,
+            vec![Ant::Queen("Sarah".to_owned()), Ant::Worker(3), Ant::Warrior]
+        );
+        let dates: Vec<Date> = parse_lines_to_struct("2022-12-01 05:00\n".as_bytes())?;
+        assert_eq!((dates[0].year, dates[0].day, dates[0].hour), (2022, 1, 5));
+        let error =
+            parse_lines_to_struct::<Date, _>("2022-12-01 05:00\nyesterday".as_bytes()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Parse error at line 2, column 1: expected a line matching"));
+        Ok(())
     }
-    println!();
 }
@@ -0,0 +1,149 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, rc::Rc};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    // times the cache filled up and was emptied
+    pub clears: u64,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )?;
+        if self.clears > 0 {
+            write!(f, ", {} clears", self.clears)?;
+        }
+        Ok(())
+    }
+}
+
+type Compute<'a, K, V> = Rc<dyn Fn(&mut Memo<'a, K, V>, &K) -> V + 'a>;
+
+// A recursive function with its results cached by key. The function gets the
+// memo back so it can recurse with memo.get(child_key), e.g.
+//
+//     let mut fib = Memo::new(|memo, &n: &u64| {
+//         if n < 2 { n } else { memo.get(n - 1) + memo.get(n - 2) }
+//     });
+//     fib.get(90);
+pub struct Memo<'a, K, V> {
+    cache: HashMap<K, V>,
+    limit: Option<usize>,
+    stats: MemoStats,
+    compute: Compute<'a, K, V>,
+}
+
+impl<'a, K: Eq + Hash + Clone, V: Clone> Memo<'a, K, V> {
+    pub fn new<F>(compute: F) -> Memo<'a, K, V>
+    where
+        F: Fn(&mut Memo<'a, K, V>, &K) -> V + 'a,
+    {
+        Memo {
+            cache: HashMap::new(),
+            limit: None,
+            stats: MemoStats::default(),
+            compute: Rc::new(compute),
+        }
+    }
+
+    // Empty the cache whenever it holds this many entries, trading repeated
+    // work for a ceiling on memory
+    pub fn with_limit(mut self, limit: usize) -> Memo<'a, K, V> {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let compute = Rc::clone(&self.compute);
+        let value = compute(self, &key);
+        if self.limit.is_some_and(|limit| self.cache.len() >= limit) {
+            self.cache.clear();
+            self.stats.clears += 1;
+        }
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recursion() {
+        let mut fib = Memo::new(|memo, &n: &u64| {
+            if n < 2 {
+                n
+            } else {
+                memo.get(n - 1) + memo.get(n - 2)
+            }
+        });
+        assert_eq!(fib.get(90), 2_880_067_194_370_816_120);
+        // every value is worked out once, then found again by its parent
+        assert_eq!(
+            fib.stats(),
+            MemoStats {
+                hits: 88,
+                misses: 91,
+                clears: 0
+            }
+        );
+        assert_eq!(fib.len(), 91);
+        fib.get(90);
+        assert_eq!(fib.stats().hits, 89);
+    }
+
+    #[test]
+    fn test_limit() {
+        let calls = std::cell::Cell::new(0);
+        let mut double = Memo::new(|_, &n: &u32| {
+            calls.set(calls.get() + 1);
+            n * 2
+        })
+        .with_limit(2);
+        for n in [1, 2, 1, 3, 1] {
+            double.get(n);
+        }
+        assert!(double.len() <= 2);
+        assert_eq!(double.stats().clears, 1);
+        // 1 was dropped when 3 went in, so it was worked out again
+        assert_eq!(calls.get(), 4);
+        assert_eq!(
+            double.stats().to_string(),
+            "1 hits, 4 misses (20.0% hit rate), 1 clears"
+        );
+    }
+}
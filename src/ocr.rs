@@ -0,0 +1,247 @@
+use std::{error::Error as StdError, fmt::Display, hash::Hash};
+
+use itertools::Itertools;
+use num::PrimInt;
+
+use crate::{Coordinate, SparseGrid};
+
+// Reading the capital letters some puzzles draw in lit pixels. Two fonts turn
+// up: letters 4 wide and 6 tall with a blank column between them, and letters
+// 6 wide and 10 tall with two blank columns between them. Only the letters
+// seen in puzzle answers are known.
+
+const SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE_FONT: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+// A letter that isn't in the font, as '#' and '.' rows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    // 0-based, counting every letter in the text
+    pub position: usize,
+    pub pattern: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    // the lit pixels are this many rows tall, which doesn't match either font
+    Height(usize),
+    // what could be read, with '?' for each unrecognised glyph
+    Unrecognised { text: String, glyphs: Vec<Glyph> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "Can't read letters {} pixels tall, only 6 or 10", height)
+            }
+            OcrError::Unrecognised { text, glyphs } => {
+                write!(f, "Couldn't read every letter of {:?}", text)?;
+                for glyph in glyphs {
+                    write!(f, "\nletter {}:\n{}", glyph.position + 1, glyph.pattern)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl StdError for OcrError {}
+
+// The letters spelt out by the lit pixels, with rows numbered down the page.
+// They can be anywhere, as letters are told apart by the blank columns
+// between them.
+pub fn read<C, I>(pixels: I) -> Result<String, OcrError>
+where
+    C: PrimInt + Hash,
+    I: IntoIterator<Item = Coordinate<C>>,
+{
+    let pixels: SparseGrid<C, ()> = pixels.into_iter().collect();
+    let Some(bounds) = pixels.bounds() else {
+        return Ok(String::new());
+    };
+    let lit = |row: usize, col: usize| {
+        pixels.contains(Coordinate {
+            row: bounds.min.row + C::from(row).unwrap(),
+            col: bounds.min.col + C::from(col).unwrap(),
+        })
+    };
+    let font = match bounds.height() {
+        6 => SMALL_FONT
+            .iter()
+            .map(|(c, rows)| (pattern(rows), *c))
+            .collect_vec(),
+        10 => LARGE_FONT
+            .iter()
+            .map(|(c, rows)| (pattern(rows), *c))
+            .collect_vec(),
+        height => return Err(OcrError::Height(height)),
+    };
+    let (mut text, mut glyphs) = (String::new(), vec![]);
+    let columns =
+        (0..bounds.width()).group_by(|&col| (0..bounds.height()).any(|row| lit(row, col)));
+    for (_, columns) in columns.into_iter().filter(|(lit, _)| *lit) {
+        let columns = columns.collect_vec();
+        let glyph = (0..bounds.height())
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|&col| if lit(row, col) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n");
+        match font.iter().find(|(pattern, _)| *pattern == glyph) {
+            Some((_, letter)) => text.push(*letter),
+            None => {
+                glyphs.push(Glyph {
+                    position: text.chars().count(),
+                    pattern: glyph,
+                });
+                text.push('?');
+            }
+        }
+    }
+    if glyphs.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognised { text, glyphs })
+    }
+}
+
+// The rows of a letter without the blank columns either side, like read sees
+// them
+fn pattern(rows: &[&str]) -> String {
+    let lit = |col: usize| rows.iter().any(|row| row.as_bytes()[col] == b'#');
+    let width = rows[0].len();
+    let first = (0..width).find(|&col| lit(col)).unwrap_or(0);
+    let last = (0..width).rfind(|&col| lit(col)).unwrap_or(0);
+    rows.iter().map(|row| &row[first..=last]).join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(picture: &str, offset: i32) -> Vec<Coordinate<i32>> {
+        picture
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(col, _)| Coordinate {
+                        row: row as i32 + offset,
+                        col: col as i32 + offset,
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small() {
+        let picture = "\
+.##..###..#..#.####.
+#..#.#..#.#..#.#....
+#..#.#..#.#..#.###..
+####.###..#..#.#....
+#..#.#.#..#..#.#....
+#..#.#..#..##..####.
+";
+        assert_eq!(read(pixels(picture, 0)), Ok("ARUE".to_owned()));
+        // anywhere on the screen, even with a letter that starts with a blank
+        // column
+        let picture = "\
+.###.#...
+..#..#...
+..#..#...
+..#..#...
+..#..#...
+.###.####
+";
+        assert_eq!(read(pixels(picture, -3)), Ok("IL".to_owned()));
+        assert_eq!(read(Vec::<Coordinate<i32>>::new()), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_large() {
+        let picture = "\
+#....#..######
+#....#.......#
+.#..#........#
+.#..#.......#.
+..##.......#..
+..##......#...
+.#..#....#....
+.#..#...#.....
+#....#..#.....
+#....#..######
+";
+        assert_eq!(read(pixels(picture, 10)), Ok("XZ".to_owned()));
+    }
+
+    #[test]
+    fn test_unrecognised() {
+        let picture = "\
+#..#.####
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#.#..#
+#..#.#..#
+";
+        let error = read(pixels(picture, 0)).unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::Unrecognised {
+                text: "H?".to_owned(),
+                glyphs: vec![Glyph {
+                    position: 1,
+                    pattern: "####\n#..#\n#..#\n#..#\n#..#\n#..#".to_owned(),
+                }],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Couldn't read every letter of \"H?\"\nletter 2:\n####\n#..#\n#..#\n#..#\n#..#\n#..#"
+        );
+        let error = read(pixels("#\n#\n#\n", 0)).unwrap_err();
+        assert_eq!(error, OcrError::Height(3));
+    }
+}
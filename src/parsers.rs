@@ -0,0 +1,347 @@
+use std::{error::Error as StdError, fmt::Display, str::FromStr};
+
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, line_ending, one_of},
+    combinator::{all_consuming, eof, map, map_res, opt, peek, recognize, rest},
+    error::{context, VerboseError, VerboseErrorKind},
+    multi::{many0, many1, separated_list1},
+    sequence::{pair, separated_pair, terminated, tuple},
+    Finish, Parser,
+};
+use num::PrimInt;
+
+use crate::{Coordinate, Coordinate3, Grid};
+
+// Building blocks for the nom parsers in each day, plus run to turn a parser
+// into a color_eyre::Result for the whole input
+
+// Days use these rather than nom's own, so that wrapping a parser in nom's
+// context names what it expected in the error run reports
+pub type Error<I> = VerboseError<I>;
+pub type IResult<I, O> = nom::IResult<I, O, Error<I>>;
+
+// An optionally signed whole number of any integer type
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+// "x,y" as a coordinate, so x is the column and y the row
+pub fn xy<T: PrimInt + FromStr>(input: &str) -> IResult<&str, Coordinate<T>> {
+    map(separated_pair(integer, char(','), integer), |(col, row)| {
+        Coordinate { row, col }
+    })(input)
+}
+
+// "x,y,z"
+pub fn xyz<T: PrimInt + FromStr>(input: &str) -> IResult<&str, Coordinate3<T>> {
+    map(
+        tuple((integer, char(','), integer, char(','), integer)),
+        |(x, _, y, _, z)| Coordinate3 { x, y, z },
+    )(input)
+}
+
+// One item per line
+pub fn lines<'a, O, F>(line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(
+        line_ending,
+        committed(terminated(
+            line,
+            context("the end of the line", peek(alt((line_ending, eof)))),
+        )),
+    )
+}
+
+// A list can end at a blank line or the end of the input, but a line that
+// doesn't parse is an error in that line rather than the end of the list
+fn committed<'a, O, F>(mut parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    move |input: &'a str| {
+        let blank = input.is_empty() || input.starts_with('\n') || input.starts_with("\r\n");
+        match parser.parse(input) {
+            Err(nom::Err::Error(error)) if !blank => Err(nom::Err::Failure(error)),
+            result => result,
+        }
+    }
+}
+
+// Groups of lines separated by a blank line
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+// A line of cells for each row, rows can be different lengths
+pub fn rows<'a, O, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    lines(many1(cell))
+}
+
+// As rows, but every row has to be the same length
+pub fn grid<'a, O, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    // keep where each row starts, for the error
+    let mut rows = lines(pair(peek(rest), many1(cell)));
+    move |input: &'a str| {
+        let (rest, rows) = rows(input)?;
+        let width = rows[0].1.len();
+        if let Some((line, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(failure(line, "a row as long as the first"));
+        }
+        let rows = rows.into_iter().map(|(_, row)| row).collect();
+        Grid::from_rows(rows)
+            .map(|grid| (rest, grid))
+            .map_err(|_| failure(input, "a grid"))
+    }
+}
+
+// An error that stops parsing, for checks nom can't make itself
+pub fn failure<'a>(input: &'a str, expected: &'static str) -> nom::Err<Error<&'a str>> {
+    nom::Err::Failure(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Context(expected))],
+    })
+}
+
+// How much of the offending text to show
+const SNIPPET_LENGTH: usize = 20;
+
+// Where and why the input couldn't be parsed. Days only see the text, so the
+// runner fills in the file with name_input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    // 1-based
+    pub line: usize,
+    pub column: usize,
+    // what was found there, up to the end of the line
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // An error at the start of rest, which must be a suffix of input
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = position(input, rest);
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: snippet(rest),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn in_file(self, file: impl Into<String>) -> ParseError {
+        ParseError {
+            file: Some(file.into()),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Parse error")?;
+        if let Some(file) = &self.file {
+            write!(f, " in {}", file)?;
+        }
+        write!(
+            f,
+            " at line {}, column {}: expected {} but found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.text.as_str() {
+            "" => write!(f, "the end of the line"),
+            text => write!(f, "{:?}", text),
+        }
+    }
+}
+
+impl StdError for ParseError {}
+
+fn snippet(rest: &str) -> String {
+    rest.lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(SNIPPET_LENGTH)
+        .collect()
+}
+
+// Name the input file in a parse error, any other error is left alone
+pub fn name_input(report: color_eyre::Report, file: &str) -> color_eyre::Report {
+    match report.downcast::<ParseError>() {
+        Ok(error) => error.in_file(file).into(),
+        Err(report) => report,
+    }
+}
+
+// Parse the whole input, allowing trailing newlines. Errors say where in the
+// input parsing stopped and what was expected there, from the innermost
+// context around the failure if there is one.
+pub fn run<'a, O, F>(parser: F, input: &'a str) -> color_eyre::Result<O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    match all_consuming(terminated(parser, many0(line_ending)))(input).finish() {
+        Ok((_, output)) => Ok(output),
+        Err(error) => {
+            let (rest, expected) = error
+                .errors
+                .iter()
+                .find_map(|(rest, kind)| match kind {
+                    VerboseErrorKind::Context(context) => Some((*rest, context.to_string())),
+                    _ => None,
+                })
+                .unwrap_or_else(|| {
+                    let (rest, kind) = &error.errors[0];
+                    (rest, describe(kind))
+                });
+            Err(ParseError::at(input, rest, expected).into())
+        }
+    }
+}
+
+// What nom was trying to parse, for errors without a context
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Char(c) => format!("{:?}", c),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+// For parsers that don't use nom, like Reformation or FromStr, which can only
+// say a whole line is wrong
+pub fn each_line<'a, T, E, F>(
+    input: &'a str,
+    expected: &str,
+    mut parse: F,
+) -> color_eyre::Result<Vec<T>>
+where
+    F: FnMut(&'a str) -> Result<T, E>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse(line).map_err(|_| {
+                ParseError {
+                    file: None,
+                    line: index + 1,
+                    column: 1,
+                    text: snippet(line),
+                    expected: expected.to_owned(),
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+// 1-based line and column of the start of rest, which must be a suffix of input
+pub fn position(input: &str, rest: &str) -> (usize, usize) {
+    let before = &input[..input.len() - rest.len()];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::bytes::complete::tag;
+
+    #[test]
+    fn test_numbers_and_coordinates() -> color_eyre::Result<()> {
+        assert_eq!(run(integer::<i32>, "-42")?, -42);
+        assert_eq!(run(integer::<u8>, "+7")?, 7);
+        assert!(run(integer::<u8>, "-7").is_err());
+        assert_eq!(run(xy::<u32>, "498,4")?, Coordinate { row: 4, col: 498 });
+        assert_eq!(run(xyz::<i32>, "1,-2,3")?, Coordinate3::new(1, -2, 3));
+        let path = run(
+            lines(separated_list1(tag(" -> "), xy::<i32>)),
+            "1,2 -> 3,2\n0,0\n",
+        )?;
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0][1], Coordinate { row: 2, col: 3 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocks_and_grids() -> color_eyre::Result<()> {
+        let groups = run(blocks(lines(integer::<u32>)), "1\n2\n\n3\n")?;
+        assert_eq!(groups, vec![vec![1, 2], vec![3]]);
+        let ragged = run(rows(one_of("#.")), "#.\n.\n")?;
+        assert_eq!(ragged, vec![vec!['#', '.'], vec!['.']]);
+        let square = run(grid(one_of("#.")), "#.\n.#")?;
+        assert_eq!(square.to_string(), "#.\n.#");
+        assert!(run(grid(one_of("#.")), "#.\n.").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = run(lines(xy::<u32>), "1,2\n3,x\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 3: expected a number but found \"x\""
+        );
+        let error = run(tuple((xy::<u32>, context("\" ok\"", tag(" ok")))), "1,2 no").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 1, column 4: expected \" ok\" but found \" no\""
+        );
+        // without a context nom can only say what kind of parser failed
+        let error = run(tuple((xy::<u32>, tag(" ok"))), "1,2 no").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("expected tag but found \" no\""));
+        let error = run(lines(xy::<u32>), "1,2\n3,4 5\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 4: expected the end of the line but found \" 5\""
+        );
+        let error = run(grid(one_of("#.")), "#.\n.\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 1: expected a row as long as the first but found \".\""
+        );
+        assert_eq!(position("ab\ncd", "d"), (2, 2));
+    }
+
+    #[test]
+    fn test_named_errors() {
+        let error = each_line("1\n2\nthree", "a number", str::parse::<u32>).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 3, column 1: expected a number but found \"three\""
+        );
+        let error = name_input(error, "input/day1.txt");
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.file.as_deref(), Some("input/day1.txt"));
+        assert_eq!((parse_error.line, parse_error.column), (3, 1));
+        assert!(error
+            .to_string()
+            .starts_with("Parse error in input/day1.txt at line 3"));
+        // anything else passes straight through
+        let error = name_input(color_eyre::eyre::eyre!("No answer"), "input/day1.txt");
+        assert_eq!(error.to_string(), "No answer");
+    }
+}
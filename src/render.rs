@@ -0,0 +1,374 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use num::PrimInt;
+
+use crate::{Bounds, Coordinate, Grid, Origin};
+
+// Pixel height of each line of the key under an SVG
+const KEY_LINE: usize = 16;
+// Wide enough for a short label even when the picture is narrow
+const MIN_SVG_WIDTH: usize = 160;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// How one kind of cell is drawn, as text and in images
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Style {
+    pub symbol: char,
+    pub colour: Rgb,
+    pub label: String,
+}
+
+impl Style {
+    pub fn new(symbol: char, colour: Rgb, label: &str) -> Style {
+        Style {
+            symbol,
+            colour,
+            label: label.to_owned(),
+        }
+    }
+}
+
+// Maps the kinds of cell in a picture, usually a day's own enum, to styles.
+// Cells with nothing in them get the empty style, and kinds that were never
+// added show up as a magenta '?'.
+#[derive(Clone, Debug)]
+pub struct Legend<K> {
+    empty: Style,
+    unknown: Style,
+    styles: Vec<(K, Style)>,
+}
+
+impl<K: PartialEq> Legend<K> {
+    pub fn new(empty: Style) -> Legend<K> {
+        Legend {
+            empty,
+            unknown: Style::new('?', Rgb(255, 0, 255), "unknown"),
+            styles: vec![],
+        }
+    }
+
+    pub fn with(mut self, kind: K, style: Style) -> Legend<K> {
+        self.styles.push((kind, style));
+        self
+    }
+
+    pub fn style(&self, kind: Option<&K>) -> &Style {
+        match kind {
+            None => &self.empty,
+            Some(kind) => self
+                .styles
+                .iter()
+                .find(|(k, _)| k == kind)
+                .map_or(&self.unknown, |(_, style)| style),
+        }
+    }
+
+    // Every style with its label, empty first
+    pub fn entries(&self) -> impl Iterator<Item = &Style> {
+        std::iter::once(&self.empty).chain(self.styles.iter().map(|(_, style)| style))
+    }
+
+    // One line per style, e.g. "# rock"
+    pub fn key(&self) -> String {
+        self.entries()
+            .map(|style| format!("{} {}\n", style.symbol, style.label))
+            .collect()
+    }
+}
+
+// A rectangle of cells to draw, row 0 at the top of the page
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Picture<K> {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<K>>,
+}
+
+impl<K> Picture<K> {
+    pub fn new(width: usize, height: usize) -> Picture<K> {
+        Picture {
+            width,
+            height,
+            cells: (0..width * height).map(|_| None).collect(),
+        }
+    }
+
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut kind: F) -> Picture<K>
+    where
+        F: FnMut(&T) -> Option<K>,
+    {
+        Picture {
+            width: grid.width(),
+            height: grid.height(),
+            cells: grid.iter().map(|(_, cell)| kind(cell)).collect(),
+        }
+    }
+
+    // Everything inside the bounding box of the cells, empty for no cells.
+    // Later cells are drawn over earlier ones at the same coordinate.
+    pub fn from_cells<C, I>(cells: I, origin: Origin) -> Picture<K>
+    where
+        C: PrimInt,
+        I: IntoIterator<Item = (Coordinate<C>, K)>,
+    {
+        let cells = cells.into_iter().collect_vec();
+        let bounds = cells
+            .iter()
+            .fold(None, |bounds: Option<Bounds<C>>, (c, _)| {
+                Some(match bounds {
+                    Some(mut bounds) => {
+                        bounds.expand(*c);
+                        bounds
+                    }
+                    None => Bounds::new(*c),
+                })
+            });
+        match bounds {
+            Some(bounds) => Picture::from_cells_within(cells, bounds, origin),
+            None => Picture::new(0, 0),
+        }
+    }
+
+    // A window onto the cells, anything outside it is left out
+    pub fn from_cells_within<C, I>(cells: I, bounds: Bounds<C>, origin: Origin) -> Picture<K>
+    where
+        C: PrimInt,
+        I: IntoIterator<Item = (Coordinate<C>, K)>,
+    {
+        let mut picture = Picture::new(bounds.width(), bounds.height());
+        for (coordinate, kind) in cells {
+            if !bounds.contains(coordinate) {
+                continue;
+            }
+            let row = (coordinate.row - bounds.min.row).to_usize().unwrap();
+            let row = match origin {
+                Origin::TopLeft => row,
+                Origin::BottomLeft => picture.height - 1 - row,
+            };
+            let col = (coordinate.col - bounds.min.col).to_usize().unwrap();
+            picture.set(row, col, kind);
+        }
+        picture
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&K> {
+        self.cells[row * self.width + col].as_ref()
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, kind: K) {
+        self.cells[row * self.width + col] = Some(kind);
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Option<K>]> {
+        // chunks panics on 0, and a 0 wide picture has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl<K: PartialEq> Picture<K> {
+    // One char per cell and a newline after every row
+    pub fn to_text(&self, legend: &Legend<K>) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            text.extend(row.iter().map(|cell| legend.style(cell.as_ref()).symbol));
+            text.push('\n');
+        }
+        text
+    }
+
+    // Squares of cell_size pixels, with the legend's key underneath
+    pub fn to_svg(&self, legend: &Legend<K>, cell_size: usize) -> String {
+        let (width, height) = (self.width * cell_size, self.height * cell_size);
+        let mut svg = svg_start(width, height, legend);
+        svg.push_str(&self.svg_cells(legend, cell_size));
+        svg.push_str(&svg_key(legend, height));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // The background and a rect for every cell that isn't empty
+    pub(crate) fn svg_cells(&self, legend: &Legend<K>, cell_size: usize) -> String {
+        let mut svg = String::new();
+        // writing to a String can't fail
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            self.width * cell_size,
+            self.height * cell_size,
+            legend.style(None).colour.hex()
+        );
+        for (index, row) in self.rows().enumerate() {
+            for (col, cell) in row.iter().enumerate() {
+                if let Some(kind) = cell {
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{3}" height="{3}" fill="{}"/>"#,
+                        col * cell_size,
+                        index * cell_size,
+                        legend.style(Some(kind)).colour.hex(),
+                        cell_size
+                    );
+                }
+            }
+        }
+        svg
+    }
+
+    // Binary PPM (P6), cell_size pixels square per cell. Most image viewers
+    // open these, and converting to PNG is a one liner.
+    pub fn to_ppm(&self, legend: &Legend<K>, cell_size: usize) -> Vec<u8> {
+        let (width, height) = (self.width * cell_size, self.height * cell_size);
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for row in self.rows() {
+            let line = row
+                .iter()
+                .flat_map(|cell| {
+                    let Rgb(r, g, b) = legend.style(cell.as_ref()).colour;
+                    [r, g, b].repeat(cell_size)
+                })
+                .collect_vec();
+            for _ in 0..cell_size {
+                ppm.extend_from_slice(&line);
+            }
+        }
+        ppm
+    }
+}
+
+// The opening svg element, with room for a picture of width by height pixels
+// and the key under it
+pub(crate) fn svg_start<K: PartialEq>(width: usize, height: usize, legend: &Legend<K>) -> String {
+    let total_height = height + legend.entries().count() * KEY_LINE;
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width.max(MIN_SVG_WIDTH),
+        total_height
+    )
+}
+
+// A swatch and label for every style, starting top pixels down
+pub(crate) fn svg_key<K: PartialEq>(legend: &Legend<K>, top: usize) -> String {
+    let mut svg = String::new();
+    for (line, style) in legend.entries().enumerate() {
+        let y = top + line * KEY_LINE;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="2" y="{}" width="12" height="12" fill="{}" stroke="black"/>"#,
+            y + 2,
+            style.colour.hex()
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="20" y="{}" font-family="monospace" font-size="12">{} {}</text>"#,
+            y + 12,
+            escape(&style.symbol.to_string()),
+            escape(&style.label)
+        );
+    }
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Sand,
+    }
+
+    fn legend() -> Legend<Tile> {
+        Legend::new(Style::new('.', Rgb::WHITE, "air"))
+            .with(Tile::Wall, Style::new('#', Rgb::BLACK, "rock"))
+            .with(Tile::Sand, Style::new('o', Rgb(194, 178, 128), "sand"))
+    }
+
+    fn picture() -> Picture<Tile> {
+        let cells = [
+            (Coordinate::new(0, 0), Tile::Wall),
+            (Coordinate::new(0, 1), Tile::Wall),
+            (Coordinate::new(1, 2), Tile::Sand),
+        ];
+        Picture::from_cells(cells, Origin::BottomLeft)
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(picture().to_text(&legend()), "..o\n##.\n");
+        assert_eq!(legend().key(), ". air\n# rock\no sand\n");
+        let mut sideways = Picture::new(1, 2);
+        sideways.set(1, 0, Tile::Sand);
+        // anything missing from the legend is obvious
+        assert_eq!(
+            sideways.to_text(&Legend::new(Style::new(' ', Rgb::WHITE, ""))),
+            " \n?\n"
+        );
+        assert_eq!(
+            Picture::<Tile>::from_cells(Vec::<(Coordinate<i32>, _)>::new(), Origin::TopLeft)
+                .to_text(&legend()),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_from_grid() -> color_eyre::Result<()> {
+        let grid = Grid::parse("#.\n.#", "a cell", Some)?;
+        let picture = Picture::from_grid(&grid, |&c| (c == '#').then_some(Tile::Wall));
+        assert_eq!(picture.to_text(&legend()), "#.\n.#\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = picture().to_svg(&legend(), 10);
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="160" height="68" viewBox="0 0 160 68">"#
+        ));
+        assert!(svg.contains(r##"<rect x="20" y="0" width="10" height="10" fill="#c2b280"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="10" width="10" height="10" fill="#000000"/>"##));
+        assert!(svg.contains(">o sand</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let ppm = picture().to_ppm(&legend(), 2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // top left is air, bottom left is rock
+        assert_eq!(&pixels[..3], &[255, 255, 255]);
+        assert_eq!(&pixels[3 * 6 * 3..3 * 6 * 3 + 3], &[0, 0, 0]);
+        // sand fills the top right 2x2 square
+        assert_eq!(&pixels[3 * 4..3 * 5], &[194, 178, 128]);
+        assert_eq!(&pixels[3 * 6 + 3 * 5..3 * 6 + 3 * 6], &[194, 178, 128]);
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num::Zero;
+
+// Searches over any state space described by closures. Each one takes every
+// start state at once, so "shortest from any of these" is a single search.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    // From the start that was used up to and including the goal
+    pub states: Vec<S>,
+}
+
+// Every state seen, with the state it was first reached from
+struct Visited<S> {
+    states: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Visited<S> {
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.states.push((state, parent));
+        self.states.len() - 1
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<S> {
+        let mut path = vec![self.states[index].0.clone()];
+        while let Some(parent) = self.states[index].1 {
+            path.push(self.states[parent].0.clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// Fewest steps to a goal when every move costs the same
+pub fn bfs<S, I, N, NI, G>(starts: I, mut neighbours: N, mut is_goal: G) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited { states: Vec::new() };
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back((visited.push(start, None), 0));
+        }
+    }
+    while let Some((index, steps)) = queue.pop_front() {
+        let state = visited.states[index].0.clone();
+        if is_goal(&state) {
+            return Some(Path {
+                cost: steps,
+                states: visited.path_to(index),
+            });
+        }
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back((visited.push(next, Some(index)), steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// Everything reachable from the starts, including the starts themselves
+pub fn flood_fill<S, I, N, NI>(starts: I, mut neighbours: N) -> HashSet<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = S>,
+{
+    let mut seen: HashSet<S> = HashSet::new();
+    let mut queue: VecDeque<S> = starts
+        .into_iter()
+        .filter(|start| seen.insert(start.clone()))
+        .collect();
+    while let Some(state) = queue.pop_front() {
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+// Cheapest path to a goal, neighbours returns each next state with the cost
+// of moving to it
+pub fn dijkstra<S, C, I, N, NI, G>(starts: I, neighbours: N, is_goal: G) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, neighbours, |_| C::zero(), is_goal)
+}
+
+// As dijkstra, guided by a heuristic which must never overestimate the
+// remaining cost or the result might not be the cheapest
+pub fn astar<S, C, I, N, NI, H, G>(
+    starts: I,
+    mut neighbours: N,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited { states: Vec::new() };
+    let mut best: HashMap<S, C> = HashMap::new();
+    // ordered by estimated total, then cost so far, then the visited index
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if best.insert(start.clone(), C::zero()).is_none() {
+            let estimate = heuristic(&start);
+            let index = visited.push(start, None);
+            queue.push(Reverse((estimate, C::zero(), index)));
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let state = visited.states[index].0.clone();
+        // skip anything a cheaper route has already been queued for
+        if best.get(&state).is_some_and(|&b| b < cost) {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(Path {
+                cost,
+                states: visited.path_to(index),
+            });
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if best.get(&next).is_some_and(|&b| b <= next_cost) {
+                continue;
+            }
+            best.insert(next.clone(), next_cost);
+            let estimate = next_cost + heuristic(&next);
+            let next_index = visited.push(next, Some(index));
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coordinate, Grid};
+
+    // '#' is a wall, digits cost that much to step onto
+    fn maze() -> Grid<char> {
+        Grid::parse("S.1#\n#.9.\n..1E", "a cell", Some).unwrap()
+    }
+
+    fn step_cost(c: char) -> u32 {
+        c.to_digit(10).unwrap_or(1)
+    }
+
+    fn open_neighbours(grid: &Grid<char>, c: Coordinate<usize>) -> Vec<Coordinate<usize>> {
+        grid.neighbours(c)
+            .into_iter()
+            .filter(|&n| grid[n] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        let path = bfs([start], |&c| open_neighbours(&grid, c), |&c| c == end).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!(path.states[0], start);
+        assert_eq!(path.states[5], end);
+        assert!(path
+            .states
+            .windows(2)
+            .all(|pair| pair[0].manhattan(&pair[1]) == 1));
+        // the nearest of several starts wins
+        let near = Coordinate::new(2, 2);
+        let path = bfs([start, near], |&c| open_neighbours(&grid, c), |&c| c == end).unwrap();
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.states, vec![near, end]);
+        // a start that is already a goal costs nothing
+        assert_eq!(bfs([end], |_| vec![], |&c| c == end).unwrap().cost, 0);
+        assert_eq!(bfs([start], |_| vec![], |&c| c == end), None);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let grid = maze();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        let neighbours = |c: &Coordinate<usize>| {
+            open_neighbours(&grid, *c)
+                .into_iter()
+                .map(|n| (n, step_cost(grid[n])))
+                .collect::<Vec<_>>()
+        };
+        let cheapest = dijkstra([start], neighbours, |&c| c == end).unwrap();
+        // going round the 9 is cheaper even though it's further
+        assert_eq!(cheapest.cost, 5);
+        assert!(!cheapest.states.contains(&Coordinate::new(1, 2)));
+        let guided = astar(
+            [start],
+            neighbours,
+            |c| c.manhattan(&end) as u32,
+            |&c| c == end,
+        )
+        .unwrap();
+        assert_eq!(guided, cheapest);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = maze();
+        let reached = flood_fill([Coordinate::new(0, 0)], |&c| open_neighbours(&grid, c));
+        assert_eq!(reached.len(), 10);
+        assert!(!reached.contains(&Coordinate::new(0, 3)));
+    }
+}
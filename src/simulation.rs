@@ -0,0 +1,259 @@
+use std::{fmt::Write, time::Duration};
+
+use itertools::Itertools;
+
+use crate::render::{svg_key, svg_start, Legend, Picture};
+
+// A puzzle that plays out one step at a time, like a grain of sand falling or
+// a round of elves moving, so a Recorder can capture frames of it
+pub trait Simulation {
+    // The kinds of cell in a picture, see render::Legend
+    type Kind: PartialEq;
+
+    // Take one step, or return false and leave everything alone if there's
+    // nothing left to do
+    fn step(&mut self) -> bool;
+
+    fn picture(&self) -> Picture<Self::Kind>;
+
+    fn legend(&self) -> Legend<Self::Kind>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    // Every frame as text, one after another
+    Text,
+    // A single animated image
+    Svg,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Recorder {
+    // Capture a frame every this many steps. The start and end are always
+    // captured.
+    pub every: usize,
+    // Stop after this many steps
+    pub limit: Option<usize>,
+    pub format: Format,
+    // Pixels per cell and how long each frame shows for, SVG only
+    pub cell_size: usize,
+    pub frame_time: Duration,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder {
+            every: 1,
+            limit: None,
+            format: Format::Text,
+            cell_size: 4,
+            frame_time: Duration::from_millis(100),
+        }
+    }
+}
+
+impl Recorder {
+    // For simulations that would run forever, or that the puzzle stops early,
+    // unless a limit was already asked for
+    pub fn or_limit(self, steps: usize) -> Recorder {
+        Recorder {
+            limit: Some(self.limit.unwrap_or(steps)),
+            ..self
+        }
+    }
+
+    pub fn capture<S: Simulation>(&self, simulation: &mut S) -> Recording<S::Kind> {
+        let every = self.every.max(1);
+        let mut frames = vec![Frame {
+            step: 0,
+            picture: simulation.picture(),
+        }];
+        let mut steps = 0;
+        while self.limit.is_none_or(|limit| steps < limit) && simulation.step() {
+            steps += 1;
+            if steps % every == 0 {
+                frames.push(Frame {
+                    step: steps,
+                    picture: simulation.picture(),
+                });
+            }
+        }
+        if frames.last().is_some_and(|frame| frame.step != steps) {
+            frames.push(Frame {
+                step: steps,
+                picture: simulation.picture(),
+            });
+        }
+        Recording {
+            frames,
+            legend: simulation.legend(),
+        }
+    }
+
+    // Capture the simulation and write it out in the chosen format
+    pub fn record<S: Simulation>(&self, simulation: &mut S) -> String {
+        let recording = self.capture(simulation);
+        match self.format {
+            Format::Text => recording.to_text(),
+            Format::Svg => recording.to_svg(self.cell_size, self.frame_time),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Frame<K> {
+    // how many steps had been taken
+    pub step: usize,
+    pub picture: Picture<K>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Recording<K> {
+    pub frames: Vec<Frame<K>>,
+    pub legend: Legend<K>,
+}
+
+impl<K: PartialEq> Recording<K> {
+    // Each frame under a "Step N" heading, with a blank line between frames
+    pub fn to_text(&self) -> String {
+        self.frames
+            .iter()
+            .map(|frame| {
+                format!(
+                    "Step {}\n{}",
+                    frame.step,
+                    frame.picture.to_text(&self.legend)
+                )
+            })
+            .join("\n")
+    }
+
+    // Every frame in turn, looping forever. Each frame is a group that's only
+    // visible for its slice of the loop.
+    pub fn to_svg(&self, cell_size: usize, frame_time: Duration) -> String {
+        let count = self.frames.len();
+        let width = self
+            .frames
+            .iter()
+            .map(|frame| frame.picture.width())
+            .max()
+            .unwrap_or(0);
+        let height = self
+            .frames
+            .iter()
+            .map(|frame| frame.picture.height())
+            .max()
+            .unwrap_or(0);
+        let (width, height) = (width * cell_size, height * cell_size);
+        let total = frame_time.as_secs_f64() * count as f64;
+        let mut svg = svg_start(width, height, &self.legend);
+        for (index, frame) in self.frames.iter().enumerate() {
+            let (show, hide) = (
+                index as f64 / count as f64,
+                (index + 1) as f64 / count as f64,
+            );
+            // writing to a String can't fail
+            let _ = writeln!(svg, r#"<g visibility="hidden">"#);
+            let _ = writeln!(
+                svg,
+                r#"<animate attributeName="visibility" values="hidden;visible;hidden" keyTimes="0;{:.4};{:.4}" dur="{:.3}s" calcMode="discrete" repeatCount="indefinite"/>"#,
+                show, hide, total
+            );
+            svg.push_str(&frame.picture.svg_cells(&self.legend, cell_size));
+            svg.push_str("</g>\n");
+        }
+        svg.push_str(&svg_key(&self.legend, height));
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{Rgb, Style};
+
+    // A counter that fills in one cell per step, left to right
+    struct Fill {
+        filled: usize,
+        width: usize,
+    }
+
+    impl Simulation for Fill {
+        type Kind = ();
+
+        fn step(&mut self) -> bool {
+            if self.filled == self.width {
+                return false;
+            }
+            self.filled += 1;
+            true
+        }
+
+        fn picture(&self) -> Picture<()> {
+            let mut picture = Picture::new(self.width, 1);
+            for col in 0..self.filled {
+                picture.set(0, col, ());
+            }
+            picture
+        }
+
+        fn legend(&self) -> Legend<()> {
+            Legend::new(Style::new('.', Rgb::WHITE, "empty"))
+                .with((), Style::new('#', Rgb::BLACK, "full"))
+        }
+    }
+
+    #[test]
+    fn test_capture() {
+        let recorder = Recorder {
+            every: 2,
+            ..Recorder::default()
+        };
+        let recording = recorder.capture(&mut Fill {
+            filled: 0,
+            width: 5,
+        });
+        // the start, every other step, and the end
+        let steps = recording.frames.iter().map(|f| f.step).collect_vec();
+        assert_eq!(steps, vec![0, 2, 4, 5]);
+        assert_eq!(
+            recording.to_text(),
+            "Step 0\n.....\n\nStep 2\n##...\n\nStep 4\n####.\n\nStep 5\n#####\n"
+        );
+    }
+
+    #[test]
+    fn test_limit() {
+        let recorder = Recorder::default().or_limit(2);
+        let mut fill = Fill {
+            filled: 0,
+            width: 5,
+        };
+        assert_eq!(recorder.capture(&mut fill).frames.len(), 3);
+        assert_eq!(fill.filled, 2);
+        // an explicit limit wins
+        let recorder = Recorder {
+            limit: Some(1),
+            ..Recorder::default()
+        };
+        assert_eq!(recorder.or_limit(4).limit, Some(1));
+    }
+
+    #[test]
+    fn test_svg() {
+        let recorder = Recorder {
+            format: Format::Svg,
+            cell_size: 10,
+            frame_time: Duration::from_millis(500),
+            ..Recorder::default()
+        };
+        let svg = recorder.record(&mut Fill {
+            filled: 0,
+            width: 3,
+        });
+        assert_eq!(svg.matches("<g ").count(), 4);
+        assert!(svg.contains(r#"keyTimes="0;0.2500;0.5000" dur="2.000s""#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
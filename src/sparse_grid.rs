@@ -0,0 +1,359 @@
+use std::{cell::Cell, collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+use num::{range_inclusive, PrimInt};
+
+use crate::Coordinate;
+
+// Which way up to draw a grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    // Row numbers increase down the page, like reading a puzzle input
+    TopLeft,
+    // Row numbers increase up the page, like a height above the floor
+    BottomLeft,
+}
+
+// Smallest rectangle holding a set of coordinates, inclusive at both ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds<K: PrimInt> {
+    pub min: Coordinate<K>,
+    pub max: Coordinate<K>,
+}
+
+impl<K: PrimInt> Bounds<K> {
+    pub fn new(coordinate: Coordinate<K>) -> Bounds<K> {
+        Bounds {
+            min: coordinate,
+            max: coordinate,
+        }
+    }
+
+    pub fn expand(&mut self, coordinate: Coordinate<K>) {
+        self.min.row = self.min.row.min(coordinate.row);
+        self.min.col = self.min.col.min(coordinate.col);
+        self.max.row = self.max.row.max(coordinate.row);
+        self.max.col = self.max.col.max(coordinate.col);
+    }
+
+    pub fn contains(&self, coordinate: Coordinate<K>) -> bool {
+        (self.min.row..=self.max.row).contains(&coordinate.row)
+            && (self.min.col..=self.max.col).contains(&coordinate.col)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.col - self.min.col).to_usize().unwrap() + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.row - self.min.row).to_usize().unwrap() + 1
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+}
+
+// Cells on an unbounded plane, for simulations that spread out as they run.
+// Use `()` as the value to treat it as a set of occupied coordinates.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<K: PrimInt + Hash, V> {
+    cells: HashMap<Coordinate<K>, V>,
+    // grows on insert, but removing a cell on the edge means recalculating on
+    // the next lookup
+    bounds: Cell<Option<Bounds<K>>>,
+    stale: Cell<bool>,
+}
+
+impl<K: PrimInt + Hash, V> Default for SparseGrid<K, V> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+        }
+    }
+}
+
+impl<K: PrimInt + Hash, V> SparseGrid<K, V> {
+    pub fn new() -> SparseGrid<K, V> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, coordinate: Coordinate<K>, value: V) -> Option<V> {
+        if !self.stale.get() {
+            let bounds = match self.bounds.get() {
+                Some(mut bounds) => {
+                    bounds.expand(coordinate);
+                    bounds
+                }
+                None => Bounds::new(coordinate),
+            };
+            self.bounds.set(Some(bounds));
+        }
+        self.cells.insert(coordinate, value)
+    }
+
+    pub fn remove(&mut self, coordinate: Coordinate<K>) -> Option<V> {
+        let removed = self.cells.remove(&coordinate);
+        if removed.is_some() {
+            let inside = self.bounds.get().is_some_and(|bounds| {
+                bounds.min.row < coordinate.row
+                    && coordinate.row < bounds.max.row
+                    && bounds.min.col < coordinate.col
+                    && coordinate.col < bounds.max.col
+            });
+            if !inside {
+                self.stale.set(true);
+            }
+        }
+        removed
+    }
+
+    pub fn get(&self, coordinate: Coordinate<K>) -> Option<&V> {
+        self.cells.get(&coordinate)
+    }
+
+    pub fn get_mut(&mut self, coordinate: Coordinate<K>) -> Option<&mut V> {
+        self.cells.get_mut(&coordinate)
+    }
+
+    pub fn contains(&self, coordinate: Coordinate<K>) -> bool {
+        self.cells.contains_key(&coordinate)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate<K>, &V)> {
+        self.cells
+            .iter()
+            .map(|(&coordinate, value)| (coordinate, value))
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate<K>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // None while the grid is empty
+    pub fn bounds(&self) -> Option<Bounds<K>> {
+        if self.stale.get() {
+            let bounds = self
+                .cells
+                .keys()
+                .fold(None, |bounds: Option<Bounds<K>>, &c| {
+                    Some(match bounds {
+                        Some(mut bounds) => {
+                            bounds.expand(c);
+                            bounds
+                        }
+                        None => Bounds::new(c),
+                    })
+                });
+            self.bounds.set(bounds);
+            self.stale.set(false);
+        }
+        self.bounds.get()
+    }
+
+    // Empty cells inside the bounding box of the occupied ones
+    pub fn count_empty(&self) -> usize {
+        self.bounds().map_or(0, |bounds| bounds.area() - self.len())
+    }
+
+    // Up, down, left and right, skipping anything past the limits of K
+    pub fn neighbours(&self, coordinate: Coordinate<K>) -> Vec<Coordinate<K>> {
+        offsets(coordinate, &[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    }
+
+    // As neighbours, plus the four diagonals
+    pub fn neighbours_diagonal(&self, coordinate: Coordinate<K>) -> Vec<Coordinate<K>> {
+        offsets(
+            coordinate,
+            &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (-1, 1),
+                (1, -1),
+                (1, 1),
+            ],
+        )
+    }
+
+    // Neighbours, including diagonals, which hold a value
+    pub fn occupied_neighbours(
+        &self,
+        coordinate: Coordinate<K>,
+    ) -> impl Iterator<Item = (Coordinate<K>, &V)> {
+        self.neighbours_diagonal(coordinate)
+            .into_iter()
+            .filter_map(|c| self.get(c).map(|value| (c, value)))
+    }
+
+    // Draw the bounding box of the grid, one char per cell
+    pub fn render<F>(&self, origin: Origin, f: F) -> String
+    where
+        F: Fn(Option<&V>) -> char,
+    {
+        match self.bounds() {
+            Some(bounds) => self.render_within(bounds, origin, f),
+            None => String::new(),
+        }
+    }
+
+    // Draw any window onto the grid, e.g. to keep the origin in view
+    pub fn render_within<F>(&self, bounds: Bounds<K>, origin: Origin, f: F) -> String
+    where
+        F: Fn(Option<&V>) -> char,
+    {
+        let rows = range_inclusive(bounds.min.row, bounds.max.row).collect_vec();
+        let rows = match origin {
+            Origin::TopLeft => rows,
+            Origin::BottomLeft => rows.into_iter().rev().collect_vec(),
+        };
+        let mut result = String::new();
+        for row in rows {
+            for col in range_inclusive(bounds.min.col, bounds.max.col) {
+                result.push(f(self.get(Coordinate { row, col })));
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<K: PrimInt + Hash> SparseGrid<K, ()> {
+    // '#' for occupied cells and '.' for empty ones
+    pub fn render_occupied(&self, origin: Origin) -> String {
+        self.render(origin, |cell| if cell.is_some() { '#' } else { '.' })
+    }
+}
+
+fn offsets<K: PrimInt>(coordinate: Coordinate<K>, deltas: &[(i8, i8)]) -> Vec<Coordinate<K>> {
+    let step = |value: K, delta: i8| match delta {
+        -1 => value.checked_sub(&K::one()),
+        1 => value.checked_add(&K::one()),
+        _ => Some(value),
+    };
+    deltas
+        .iter()
+        .filter_map(|&(row, col)| {
+            Some(Coordinate {
+                row: step(coordinate.row, row)?,
+                col: step(coordinate.col, col)?,
+            })
+        })
+        .collect()
+}
+
+// Only the cells matter, not how the bounds were last worked out
+impl<K: PrimInt + Hash, V: PartialEq> PartialEq for SparseGrid<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<K: PrimInt + Hash, V: Eq> Eq for SparseGrid<K, V> {}
+
+impl<K: PrimInt + Hash, V> FromIterator<(Coordinate<K>, V)> for SparseGrid<K, V> {
+    fn from_iter<I: IntoIterator<Item = (Coordinate<K>, V)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<K: PrimInt + Hash> FromIterator<Coordinate<K>> for SparseGrid<K, ()> {
+    fn from_iter<I: IntoIterator<Item = Coordinate<K>>>(iter: I) -> Self {
+        iter.into_iter().map(|c| (c, ())).collect()
+    }
+}
+
+impl<K: PrimInt + Hash, V> Extend<(Coordinate<K>, V)> for SparseGrid<K, V> {
+    fn extend<I: IntoIterator<Item = (Coordinate<K>, V)>>(&mut self, iter: I) {
+        for (coordinate, value) in iter {
+            self.insert(coordinate, value);
+        }
+    }
+}
+
+impl<K: PrimInt + Hash> Extend<Coordinate<K>> for SparseGrid<K, ()> {
+    fn extend<I: IntoIterator<Item = Coordinate<K>>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|c| (c, ())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(row: i32, col: i32) -> Coordinate<i32> {
+        Coordinate { row, col }
+    }
+
+    #[test]
+    fn test_bounds_follow_inserts_and_removes() {
+        let mut grid: SparseGrid<i32, ()> = [c(0, 0), c(-2, 3), c(1, -1)].into_iter().collect();
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: c(-2, -1),
+                max: c(1, 3)
+            })
+        );
+        assert_eq!(grid.count_empty(), 4 * 5 - 3);
+        grid.remove(c(-2, 3));
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: c(0, -1),
+                max: c(1, 0)
+            })
+        );
+        grid.remove(c(0, 0));
+        grid.remove(c(1, -1));
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.count_empty(), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid: SparseGrid<i32, ()> = [c(0, 0), c(1, 1), c(5, 5)].into_iter().collect();
+        assert_eq!(grid.neighbours(c(0, 0)).len(), 4);
+        assert_eq!(grid.neighbours_diagonal(c(0, 0)).len(), 8);
+        let occupied = grid
+            .occupied_neighbours(c(0, 1))
+            .map(|(c, _)| c)
+            .sorted_by_key(|c| (c.row, c.col))
+            .collect_vec();
+        assert_eq!(occupied, vec![c(0, 0), c(1, 1)]);
+        // unsigned coordinates stop at zero
+        let unsigned: SparseGrid<u32, ()> = SparseGrid::new();
+        let corner = Coordinate { row: 0, col: 0 };
+        assert_eq!(unsigned.neighbours_diagonal(corner).len(), 3);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: SparseGrid<i32, ()> = [c(0, 0), c(1, 1), c(1, 2)].into_iter().collect();
+        assert_eq!(grid.render_occupied(Origin::TopLeft), "#..\n.##\n");
+        assert_eq!(grid.render_occupied(Origin::BottomLeft), ".##\n#..\n");
+        let window = Bounds {
+            min: c(-1, 0),
+            max: c(0, 1),
+        };
+        let drawn = grid.render_within(window, Origin::TopLeft, |cell| match cell {
+            Some(_) => 'x',
+            None => ' ',
+        });
+        assert_eq!(drawn, "  \nx \n");
+    }
+}
@@ -1,46 +1,50 @@
-use std::time::Instant;
+use crate::Solution;
 
 #[derive(Debug, Clone)]
 pub struct Input {}
 
-fn main() -> color_eyre::Result<()> {
-    let input = parsing::parse_input(include_str!("../../input/example.txt"))?;
-    let time = Instant::now();
-    println!(
-        "Part 1: {} in {}ms",
-        solve_part1(&input),
-        time.elapsed().as_millis()
-    );
-    let time = Instant::now();
-    println!(
-        "Part 2: {} in {}ms",
-        solve_part2(&input),
-        time.elapsed().as_millis()
-    );
-    Ok(())
+// Copy to src/days/dayN.rs, rename and add it to DAYS in src/days/mod.rs
+pub struct Day0;
+
+impl Solution for Day0 {
+    const DAY: u32 = 0;
+    type Input<'a> = Vec<Input>;
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input<'_>> {
+        parsing::parse_input(input)
+    }
+
+    fn solve_part1(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part1(input))
+    }
+
+    fn solve_part2(input: &Self::Input<'_>, _params: &()) -> color_eyre::Result<u32> {
+        Ok(solve_part2(input))
+    }
 }
 
-mod parsing {
+pub mod parsing {
     use super::Input;
-    use nom::{bytes::complete::tag, combinator::map, Finish, IResult};
+    use crate::parsers::{lines, run, IResult};
+    use nom::{bytes::complete::tag, combinator::map};
 
     fn parse_line(input: &str) -> IResult<&str, Input> {
         map(tag(" -> "), |_| Input {})(input)
     }
 
     pub fn parse_input(input: &str) -> color_eyre::Result<Vec<Input>> {
-        Ok(input
-            .lines()
-            .map(|line| parse_line(line).finish().unwrap().1)
-            .collect())
+        run(lines(parse_line), input)
     }
 }
 
-fn solve_part1(input: &[Input]) -> u32 {
+pub fn solve_part1(input: &[Input]) -> u32 {
     1
 }
 
-fn solve_part2(input: &[Input]) -> u32 {
+pub fn solve_part2(input: &[Input]) -> u32 {
     1
 }
 